@@ -3,64 +3,272 @@ use crate::errors::PlinkoError;
 use solana_program::keccak;
 use solana_program::keccak::hashv;
 
-#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
 pub enum Status {
-    Waiting,
-    Processing,
-    Finished,
+    #[default]
+    Waiting, // Created, randomness not requested yet
+    Processing, // Randomness requested, awaiting settlement
+    Settled, // Paid out against the VRF result
+    Refunded, // Stake returned to the player after the VRF timeout
+    Cancelled, // Stake returned to the player by the owner
 }
 
-impl<> Default for Status {
-    fn default() -> Self {
-        return Status::Waiting;
-    }
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum RiskLevel {
+    Low,
+    Medium,
+    High,
+}
+
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FeeModel {
+    #[default]
+    Stake, // Platform fee on the bet, charged up front
+    Winnings, // Winnings fee on the player's net winnings, charged at settlement
+    Hybrid, // Platform fee up front plus the winnings fee at settlement
+}
+
+/// Account layout versions, `migrate` rewrites accounts from older layouts to `VERSION`
+pub trait Versioned {
+    const VERSION: u8;
+    const SPACE: usize;
+}
+
+impl Versioned for House {
+    const VERSION: u8 = 2;
+    const SPACE: usize = 8 + House::LEN;
+}
+
+impl Versioned for PlinkoStatus {
+    const VERSION: u8 = 2;
+    const SPACE: usize = 8 + PlinkoStatus::LEN;
+}
+
+impl Versioned for MintConfig {
+    const VERSION: u8 = 2;
+    const SPACE: usize = 8 + MintConfig::LEN;
+}
+
+impl Versioned for PayoutTable {
+    const VERSION: u8 = 2;
+    const SPACE: usize = 8 + PayoutTable::LEN;
+}
+
+impl Versioned for Game {
+    const VERSION: u8 = 2;
+    const SPACE: usize = 8 + Game::LEN;
+}
+
+impl Versioned for UserStats {
+    const VERSION: u8 = 2;
+    const SPACE: usize = 8 + UserStats::LEN;
+}
+
+impl Versioned for HistoryPage {
+    const VERSION: u8 = 2;
+    const SPACE: usize = 8 + HistoryPage::LEN;
+}
+
+impl Versioned for Roles {
+    const VERSION: u8 = 2;
+    const SPACE: usize = 8 + Roles::LEN;
+}
+
+impl Versioned for PendingChange {
+    const VERSION: u8 = 2;
+    const SPACE: usize = 8 + PendingChange::LEN;
+}
+
+impl Versioned for Multisig {
+    const VERSION: u8 = 2;
+    const SPACE: usize = 8 + Multisig::LEN;
+}
+
+impl Versioned for MultisigTransaction {
+    const VERSION: u8 = 2;
+    const SPACE: usize = 8 + MultisigTransaction::LEN;
+}
+
+impl Versioned for Referral {
+    const VERSION: u8 = 2;
+    const SPACE: usize = 8 + Referral::LEN;
+}
+
+impl Versioned for IntegratorStats {
+    const VERSION: u8 = 2;
+    const SPACE: usize = 8 + IntegratorStats::LEN;
+}
+
+impl Versioned for FeeDistribution {
+    const VERSION: u8 = 2;
+    const SPACE: usize = 8 + FeeDistribution::LEN;
+}
+
+impl Versioned for Jackpot {
+    const VERSION: u8 = 2;
+    const SPACE: usize = 8 + Jackpot::LEN;
+}
+
+impl Versioned for FeeTiers {
+    const VERSION: u8 = 2;
+    const SPACE: usize = 8 + FeeTiers::LEN;
 }
 
 #[account]
 pub struct House {
+    pub version: u8, // Layout version of the account
     pub owner: Pubkey, // House owner
     pub balance: u64, // Total house balance
-    pub maximum_payout: u64, // Maximum payout allowed
+    pub max_liability_bps: u64, // Max pending liability as a share of the vault (10,000 = 100%)
     pub total_payout: u64, // Total payouts made
     pub withdrawals_pause: bool, // Whether withdrawals are paused
     pub pending_request: u32, // Number of pending random requests
+    pub pending_liability: u64, // Worst-case payout of all games awaiting randomness
+    pub lp_enabled: bool, // Whether the bankroll is owned by liquidity providers through LP shares
+    pub unclaimed_rakeback: u64, // Rakeback accrued to players and not claimed yet, owed by the vault
+    pub fee_refunds_owed: u64, // Platform fees the vault refunded for the fee side, owed back to LPs
 }
 
 impl House {
     pub const LEN: usize =
         8 + //discriminator
+        1 + // version
         32 + // owner
         8 + // balance
-        8 + // maximum_payout
+        8 + // max_liability_bps
         8 + // total_payout
         1 + // withdrawals_pause
-        4; // pending_request
+        4 + // pending_request
+        8 + // pending_liability
+        1 + // lp_enabled
+        8 + // unclaimed_rakeback
+        8; // fee_refunds_owed
+
+    pub fn reserve_liability(&mut self, max_payout: u64, vault_balance: u64) -> Result<()> {
+        self.pending_liability = checked_liability(
+            self.pending_liability,
+            max_payout,
+            vault_balance,
+            self.max_liability_bps
+        )?;
+        Ok(())
+    }
+
+    pub fn release_liability(&mut self, max_payout: u64) {
+        self.pending_liability = self.pending_liability.saturating_sub(max_payout);
+    }
+
+    // Platform fees belong to the fee side, so once LPs own the vault a fee it refunds is only
+    // advanced and does not lower the share price
+    pub fn advance_fee_refund(&mut self, amount: u64) {
+        if self.lp_enabled {
+            self.fee_refunds_owed = self.fee_refunds_owed.saturating_add(amount);
+        }
+    }
+
+    // Once LPs own the bankroll, funds only leave through share redemptions
+    pub fn check_withdrawal(&self, amount: u64, vault_lamports: u64) -> Result<()> {
+        require!(!self.lp_enabled, PlinkoError::LiquidityPoolActive);
+        require!(!self.withdrawals_pause, PlinkoError::WithdrawalsPaused);
+        self.check_redemption(amount, vault_lamports)
+    }
+
+    // The withdrawals pause only guards the owner's treasury, LPs can always leave
+    pub fn check_redemption(&self, amount: u64, vault_lamports: u64) -> Result<()> {
+        // Keep the vault rent exempt, every pending game's worst-case payout covered and
+        // the rakeback players can still claim
+        let reserved = Rent::get()?
+            .minimum_balance(0)
+            .saturating_add(self.pending_liability)
+            .saturating_add(self.unclaimed_rakeback);
+        require!(
+            amount <= vault_lamports.saturating_sub(reserved),
+            PlinkoError::WithdrawalExceedsAvailable
+        );
+        Ok(())
+    }
+
+    // Value backing the LP shares, everything in the vault above its rent-exempt minimum
+    // that is not owed to players as rakeback, plus the fee refunds the fee side owes back
+    pub fn net_asset_value(&self, vault_lamports: u64) -> Result<u64> {
+        Ok(
+            vault_lamports
+                .saturating_sub(Rent::get()?.minimum_balance(0))
+                .saturating_sub(self.unclaimed_rakeback)
+                .saturating_add(self.fee_refunds_owed)
+        )
+    }
+
+    pub fn shares_for_deposit(amount: u64, share_supply: u64, net_asset_value: u64) -> Result<u64> {
+        if share_supply == 0 {
+            return Ok(amount);
+        }
+        // Shares with nothing behind them cannot be priced, a deposit would go to their holders
+        require!(net_asset_value > 0, PlinkoError::EmptyLiquidityPool);
+        let shares = ((amount as u128) * (share_supply as u128)) / (net_asset_value as u128);
+        u64::try_from(shares).map_err(|_| PlinkoError::InvalidValue.into())
+    }
+
+    pub fn lamports_for_shares(shares: u64, share_supply: u64, net_asset_value: u64) -> Result<u64> {
+        require!(shares <= share_supply && share_supply > 0, PlinkoError::InvalidValue);
+        let lamports = ((shares as u128) * (net_asset_value as u128)) / (share_supply as u128);
+        u64::try_from(lamports).map_err(|_| PlinkoError::InvalidValue.into())
+    }
+}
+
+pub fn checked_liability(
+    pending_liability: u64,
+    max_payout: u64,
+    vault_balance: u64,
+    max_liability_bps: u64
+) -> Result<u64> {
+    let pending = pending_liability
+        .checked_add(max_payout)
+        .ok_or(PlinkoError::ExposureLimitExceeded)?;
+    let limit = ((vault_balance as u128) * (max_liability_bps as u128)) / 10_000;
+
+    require!((pending as u128) <= limit, PlinkoError::ExposureLimitExceeded);
+
+    Ok(pending)
 }
 
 #[account]
 pub struct PlinkoStatus {
+    pub version: u8, // Layout version of the account
     pub owner: Pubkey, // Owner of the game
     pub platform_fee: u64, // Platform fee for the (300 = 3%)
     pub fee_denominator: u64, // Denominator for fee calculation (10,000 = 100%)
     pub payout_denominator: u64, // Denominator for payout calculation
     pub min_buy_in: u64, // Minimum buy-in amount
     pub max_balls: u8, // Maximum balls per game
-    pub odds_locked: bool, // Whether odds are locked
+    pub odds_locked: bool, // Whether all payout tables are locked
     pub paused: bool, // Whether the game is paused
-    pub bucket_weights: Vec<u64>, // Weights of each bucket for random distribution
-    pub payouts: Vec<u64>, // Payouts for each bucket
     pub total_games: u64, // Total number of games played
     pub total_volume: u64, // Total volume of bets
     pub total_payouts: u64, // Total payouts made
     pub fee_treasury: Pubkey, // Treasury for platform fees
     pub house_account: Pubkey, // House account for the game
-    pub force: [u8; 32],
-    pub status: Status,
+    pub refund_timeout: i64, // Seconds after which an unfulfilled game can be refunded
+    pub refund_platform_fee: bool, // Whether refunds also return the platform fee
+    pub game_retention: i64, // Seconds after a game ends before anyone can close it
+    pub pending_owner: Pubkey, // Proposed new owner (default pubkey = no transfer in progress)
+    pub change_delay: i64, // Seconds a queued parameter change waits before it can be executed
+    pub next_change_id: u64, // Id of the next queued parameter change
+    pub referral_fee: u64, // Share of the platform fee paid to the player's referrer (10,000 = 100%)
+    pub max_integrator_fee: u64, // Highest fee a frontend can add to a bet (300 = 3%)
+    pub fee_model: FeeModel, // Whether fees are charged on the bet, on net winnings or both
+    pub winnings_fee: u64, // Fee on net winnings under the winnings and hybrid models (500 = 5%)
+    pub jackpot_fee: u64, // Share of each bet added to the jackpot pool (100 = 1%, 0 = disabled)
+    pub jackpot_odds: u64, // A contributing game wins the jackpot with odds of 1 in `jackpot_odds`
 }
 
 impl PlinkoStatus {
+    pub const DEFAULT_REFUND_TIMEOUT: i64 = 60 * 60; // 1 hour
+    pub const DEFAULT_GAME_RETENTION: i64 = 30 * 24 * 60 * 60; // 30 days
+
     pub const LEN: usize =
         8 + // discriminator
+        1 + // version
         32 + // owner
         8 + // platform_fee
         8 + // fee_denominator
@@ -69,20 +277,81 @@ impl PlinkoStatus {
         1 + // max_balls
         1 + // odds_locked
         1 + // paused
-        4 * 64 + // bucket_weights (max size of 64)
-        8 * 64 + // payouts (max size of 64)
         8 + // total_games
         8 + // total_volume
         8 + // total_payouts
         32 + // fee_treasury
         32 + // house_account
-        32 +
-        1;
+        8 + // refund_timeout
+        1 + // refund_platform_fee
+        8 + // game_retention
+        32 + // pending_owner
+        8 + // change_delay
+        8 + // next_change_id
+        8 + // referral_fee
+        8 + // max_integrator_fee
+        1 + // fee_model
+        8 + // winnings_fee
+        8 + // jackpot_fee
+        8; // jackpot_odds
 
     pub fn is_owner(&self, user: &Pubkey) -> bool {
         self.owner == *user
     }
 
+    // With a change delay set, parameters can only change through the queue
+    pub fn is_timelocked(&self) -> bool {
+        self.change_delay > 0
+    }
+
+    // Fee charged up front on the bet, none when only winnings are charged
+    pub fn stake_fee(&self, total_bet: u64, platform_fee: u64) -> u64 {
+        match self.fee_model {
+            FeeModel::Stake | FeeModel::Hybrid => (total_bet * platform_fee) / self.fee_denominator,
+            FeeModel::Winnings => 0,
+        }
+    }
+
+    // Fee on what the player wins above their bet, charged at settlement
+    pub fn winnings_fee_amount(&self, payout: u64, bet_amount: u64) -> Result<u64> {
+        let net_winnings = payout.saturating_sub(bet_amount) as u128;
+        let fee = match self.fee_model {
+            FeeModel::Stake => 0,
+            FeeModel::Winnings | FeeModel::Hybrid => {
+                (net_winnings * (self.winnings_fee as u128)) / (self.fee_denominator as u128)
+            }
+        };
+        u64::try_from(fee).map_err(|_| PlinkoError::InvalidValue.into())
+    }
+
+    // Part of the bet that feeds the jackpot pool
+    pub fn jackpot_contribution(&self, total_bet: u64) -> u64 {
+        (((total_bet as u128) * (self.jackpot_fee as u128)) / (self.fee_denominator as u128)) as u64
+    }
+
+    // Separate roll from the game's randomness so the jackpot is independent of the buckets
+    pub fn is_jackpot_hit(&self, base: u64) -> bool {
+        if self.jackpot_odds == 0 {
+            return false;
+        }
+        let hash = hashv(&[&base.to_le_bytes(), b"jackpot"]);
+        let roll = u64::from_le_bytes(hash.0[0..8].try_into().unwrap());
+        roll % self.jackpot_odds == 0
+    }
+
+    // Part of a platform fee that goes to the referrer, the rest goes to the treasury
+    pub fn referral_share(&self, platform_fee_amount: u64) -> Result<u64> {
+        let share =
+            ((platform_fee_amount as u128) * (self.referral_fee as u128)) /
+            (self.fee_denominator as u128);
+        u64::try_from(share).map_err(|_| PlinkoError::InvalidValue.into())
+    }
+
+    // The owner holds every role, other keys need it granted in the roles account
+    pub fn has_role(&self, user: &Pubkey, roles: Option<&Roles>, role: Role) -> bool {
+        self.is_owner(user) || roles.is_some_and(|roles| roles.has_role(user, role))
+    }
+
     pub fn derive_many_randoms(&self, base: u64, count: usize) -> Vec<u16> {
         let mut output = Vec::with_capacity(count);
         for i in 0..count {
@@ -95,15 +364,302 @@ impl PlinkoStatus {
         output
     }
 
-    pub fn max_bucket_value(&self) -> Result<u8> {
-        if self.bucket_weights.is_empty() {
-            return Ok(0);
+    pub fn derive_many_paths(&self, base: u64, count: usize, rows: u8) -> Vec<u16> {
+        // Bit `r` of a path is the direction taken at peg row `r` (1 = right)
+        let mask = u16::MAX >> (16 - rows as u32);
+        self.derive_many_randoms(base, count)
+            .into_iter()
+            .map(|random| random & mask)
+            .collect()
+    }
+
+    pub fn generate_request_id(&self, nonce: u64, player: Pubkey) -> u64 {
+        let mut data = Vec::new();
+        data.extend_from_slice(&nonce.to_le_bytes());
+        data.extend_from_slice(player.as_ref());
+        data.extend_from_slice(&Clock::get().unwrap().unix_timestamp.to_le_bytes());
+
+        let hash = keccak::hash(&data);
+        u64::from_le_bytes(hash.to_bytes()[0..8].try_into().unwrap())
+    }
+}
+
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Pauser, // Can pause and unpause the game
+    OddsManager, // Can set and lock payout tables
+    Operator, // Can set fees, limits and mints, and cancel stuck games
+    Treasurer, // Can withdraw from the vaults
+}
+
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RoleMember {
+    pub member: Pubkey, // Key the roles are granted to
+    pub roles: u8, // Bitmask of granted roles, bit `role as u8`
+}
+
+#[account]
+pub struct Roles {
+    pub version: u8, // Layout version of the account
+    pub members: Vec<RoleMember>, // Keys holding at least one role
+    pub bump: u8, // PDA bump
+}
+
+impl Roles {
+    pub const MAX_MEMBERS: usize = 16;
+
+    pub const LEN: usize =
+        8 + // discriminator
+        1 + // version
+        4 +
+        (32 + 1) * Roles::MAX_MEMBERS + // members
+        1; // bump
+
+    pub fn has_role(&self, user: &Pubkey, role: Role) -> bool {
+        self.members
+            .iter()
+            .any(|m| m.member == *user && m.roles & (1 << (role as u8)) != 0)
+    }
+
+    pub fn init_if_new(&mut self, bump: u8) {
+        if self.version == 0 {
+            self.version = Roles::VERSION;
+            self.members = Vec::new();
+            self.bump = bump;
+        }
+    }
+
+    pub fn grant(&mut self, member: Pubkey, role: Role) -> Result<()> {
+        match self.members.iter_mut().find(|m| m.member == member) {
+            Some(entry) => {
+                entry.roles |= 1 << (role as u8);
+            }
+            None => {
+                require!(self.members.len() < Roles::MAX_MEMBERS, PlinkoError::RolesFull);
+                self.members.push(RoleMember { member, roles: 1 << (role as u8) });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn revoke(&mut self, member: Pubkey, role: Role) -> Result<()> {
+        let entry = self.members
+            .iter_mut()
+            .find(|m| m.member == member && m.roles & (1 << (role as u8)) != 0)
+            .ok_or(PlinkoError::RoleNotGranted)?;
+        entry.roles &= !(1 << (role as u8));
+
+        // Drop members without any role left so their slot can be reused
+        self.members.retain(|m| m.roles != 0);
+        Ok(())
+    }
+}
+
+#[account]
+pub struct MintConfig {
+    pub version: u8, // Layout version of the account
+    pub mint: Pubkey, // SPL mint accepted for bets
+    pub token_program: Pubkey, // Token program owning the mint (Token or Token-2022)
+    pub decimals: u8, // Decimals of the mint
+    pub platform_fee: u64, // Platform fee for bets in this mint (300 = 3%)
+    pub min_buy_in: u64, // Minimum buy-in amount in base units
+    pub enabled: bool, // Whether bets in this mint are accepted
+    pub balance: u64, // House balance held in the mint vault
+    pub total_games: u64, // Total number of games played in this mint
+    pub total_volume: u64, // Total volume of bets in this mint
+    pub total_payouts: u64, // Total payouts made in this mint
+    pub pending_request: u32, // Number of pending random requests in this mint
+    pub max_liability_bps: u64, // Max pending liability as a share of the vault (10,000 = 100%)
+    pub pending_liability: u64, // Worst-case payout of all games awaiting randomness in this mint
+    pub bump: u8, // PDA bump
+    pub vault_bump: u8, // Bump of the mint vault token account
+    pub total_distributed: u64, // Fees in this mint paid out of the fee vault
+}
+
+impl MintConfig {
+    pub const LEN: usize =
+        8 + // discriminator
+        1 + // version
+        32 + // mint
+        32 + // token_program
+        1 + // decimals
+        8 + // platform_fee
+        8 + // min_buy_in
+        1 + // enabled
+        8 + // balance
+        8 + // total_games
+        8 + // total_volume
+        8 + // total_payouts
+        4 + // pending_request
+        8 + // max_liability_bps
+        8 + // pending_liability
+        1 + // bump
+        1 + // vault_bump
+        8; // total_distributed
+
+    pub fn validate(platform_fee: u64, min_buy_in: u64, max_liability_bps: u64) -> Result<()> {
+        require!(platform_fee <= 500, PlinkoError::PlatformFeeTooHigh); // Max 5%
+        require!(min_buy_in > 0, PlinkoError::InvalidValue);
+        require!(
+            max_liability_bps > 0 && max_liability_bps <= 10_000,
+            PlinkoError::InvalidValue
+        );
+        Ok(())
+    }
+
+    pub fn configure(
+        &mut self,
+        platform_fee: u64,
+        min_buy_in: u64,
+        max_liability_bps: u64,
+        enabled: bool
+    ) {
+        self.platform_fee = platform_fee;
+        self.min_buy_in = min_buy_in;
+        self.max_liability_bps = max_liability_bps;
+        self.enabled = enabled;
+    }
+
+    pub fn reserve_liability(&mut self, max_payout: u64, vault_balance: u64) -> Result<()> {
+        self.pending_liability = checked_liability(
+            self.pending_liability,
+            max_payout,
+            vault_balance,
+            self.max_liability_bps
+        )?;
+        Ok(())
+    }
+
+    pub fn release_liability(&mut self, max_payout: u64) {
+        self.pending_liability = self.pending_liability.saturating_sub(max_payout);
+    }
+
+    pub fn check_withdrawal(&self, amount: u64, vault_amount: u64) -> Result<()> {
+        // Token vaults have no rent of their own, only pending games are reserved
+        require!(
+            amount <= vault_amount.saturating_sub(self.pending_liability),
+            PlinkoError::WithdrawalExceedsAvailable
+        );
+        Ok(())
+    }
+}
+
+#[account]
+pub struct PayoutTable {
+    pub version: u8, // Layout version of the account
+    pub risk_level: RiskLevel, // Risk level the table is offered under
+    pub rows: u8, // Peg rows of the board (0 = weighted bucket mode)
+    pub bucket_weights: Vec<u64>, // Cumulative bucket weights (weighted bucket mode only)
+    pub payouts: Vec<u64>, // Payouts for each bucket
+    pub locked: bool, // Whether this table can still be replaced
+    pub bump: u8, // PDA bump
+}
+
+impl PayoutTable {
+    pub const MIN_ROWS: u8 = 8;
+    pub const MAX_ROWS: u8 = 16;
+    pub const MAX_BUCKETS: usize = 64;
+
+    pub const LEN: usize =
+        8 + // discriminator
+        1 + // version
+        1 + // risk_level
+        1 + // rows
+        4 +
+        8 * PayoutTable::MAX_BUCKETS + // bucket_weights
+        4 +
+        8 * PayoutTable::MAX_BUCKETS + // payouts
+        1 + // locked
+        1; // bump
+
+    pub fn is_configured(&self) -> bool {
+        !self.payouts.is_empty()
+    }
+
+    pub fn validate(rows: u8, bucket_weights: &[u64], payouts: &[u64]) -> Result<()> {
+        require!(!payouts.is_empty(), PlinkoError::InvalidLength);
+        require!(payouts.len() <= PayoutTable::MAX_BUCKETS, PlinkoError::InvalidLength);
+
+        if rows == 0 {
+            require!(bucket_weights.len() == payouts.len(), PlinkoError::InvalidLength);
+
+            for i in 1..bucket_weights.len() {
+                require!(
+                    bucket_weights[i] > bucket_weights[i - 1],
+                    PlinkoError::InvalidBucketIndex
+                );
+            }
+            // Buckets are drawn from 16 bits of randomness
+            require!(
+                bucket_weights.last().is_some_and(|weight| *weight <= (u16::MAX as u64)),
+                PlinkoError::InvalidBucketIndex
+            );
+        } else {
+            // Board mode: one bucket per possible number of right turns
+            require!(
+                (PayoutTable::MIN_ROWS..=PayoutTable::MAX_ROWS).contains(&rows),
+                PlinkoError::InvalidRows
+            );
+            require!(bucket_weights.is_empty(), PlinkoError::InvalidLength);
+            require!(payouts.len() == (rows as usize) + 1, PlinkoError::InvalidLength);
+        }
+
+        for payout in payouts {
+            require!(*payout <= 10_000_000, PlinkoError::InvalidBucketIndex); // Max 100x
+        }
+
+        Ok(())
+    }
+
+    pub fn configure(
+        &mut self,
+        risk_level: RiskLevel,
+        rows: u8,
+        bucket_weights: Vec<u64>,
+        payouts: Vec<u64>,
+        bump: u8
+    ) {
+        self.version = PayoutTable::VERSION;
+        self.risk_level = risk_level;
+        self.rows = rows;
+        self.bucket_weights = bucket_weights;
+        self.payouts = payouts;
+        self.locked = false;
+        self.bump = bump;
+    }
+
+    pub fn get_path_bucket_index(&self, path: u16) -> Result<u8> {
+        if self.rows == 0 {
+            return Err(PlinkoError::InvalidRows.into());
+        }
+
+        // The ball lands in the bucket matching its number of right turns
+        Ok(path.count_ones() as u8)
+    }
+
+    pub fn max_payout_amount(
+        &self,
+        bet_amount_per_ball: u64,
+        num_balls: u8,
+        payout_denominator: u64
+    ) -> Result<u64> {
+        let max_multiplier = self.payouts.iter().copied().max().unwrap_or(0);
+        let max_payout =
+            ((bet_amount_per_ball as u128) * (max_multiplier as u128) * (num_balls as u128)) /
+            (payout_denominator as u128);
+
+        u64::try_from(max_payout).map_err(|_| PlinkoError::ExposureLimitExceeded.into())
+    }
+
+    pub fn max_bucket_value(&self) -> Result<u16> {
+        match self.bucket_weights.last() {
+            Some(weight) => u16::try_from(*weight).map_err(|_| PlinkoError::InvalidBucketIndex.into()),
+            None => Ok(0),
         }
-        Ok(self.bucket_weights[self.bucket_weights.len() - 1] as u8)
     }
 
     pub fn get_bucket_index(&self, random: u16) -> Result<u8> {
-        let max_bucket_val = self.max_bucket_value()? as u16;
+        let max_bucket_val = self.max_bucket_value()?;
         if max_bucket_val == 0 {
             return Err(PlinkoError::InvalidBucketIndex.into());
         }
@@ -120,75 +676,962 @@ impl PlinkoStatus {
         Ok(len as u8)
     }
 
-    pub fn get_payout_amount(&self, bet_amount: u64, bucket_index: u8) -> Result<u64> {
+    pub fn get_payout_amount(
+        &self,
+        bet_amount: u64,
+        bucket_index: u8,
+        payout_denominator: u64
+    ) -> Result<u64> {
         if (bucket_index as usize) >= self.payouts.len() {
             return Err(PlinkoError::InvalidBucketIndex.into());
         }
 
-        Ok((bet_amount * self.payouts[bucket_index as usize]) / self.payout_denominator)
+        Ok((bet_amount * self.payouts[bucket_index as usize]) / payout_denominator)
     }
+}
 
-    pub fn generate_request_id(&self, game_id: u64, player: Pubkey) -> u64 {
-        let mut data = Vec::new();
-        data.extend_from_slice(&game_id.to_le_bytes());
-        data.extend_from_slice(player.as_ref());
-        data.extend_from_slice(&Clock::get().unwrap().unix_timestamp.to_le_bytes());
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum ParameterChange {
+    PlatformFee(u64), // New platform fee in basis points
+    MinBuyIn(u64), // New minimum buy-in
+    MaxBalls(u8), // New maximum balls per game
+    ChangeDelay(i64), // New delay for queued changes
+    ReferralFee(u64), // New referrer share of the platform fee in basis points
+    MaxIntegratorFee(u64), // New cap on integrator fees in basis points
+    FeeTreasury(Pubkey), // New wallet receiving platform fees
+    FeeModel {
+        fee_model: FeeModel,
+        winnings_fee: u64,
+    }, // New fee model and winnings fee in basis points
+    Jackpot {
+        jackpot_fee: u64,
+        jackpot_odds: u64,
+    }, // New jackpot contribution in basis points and odds of a hit
+    Payout {
+        risk_level: RiskLevel,
+        rows: u8,
+        bucket_weights: Vec<u64>,
+        payouts: Vec<u64>,
+    }, // New payout table, same arguments as `set_payout`
+    FeeDistribution(Vec<FeeRecipient>), // New fee recipients, same arguments as `set_fee_distribution`
+    FeeTiers(Vec<FeeTier>), // New volume tiers, same arguments as `set_fee_tiers`
+    MintConfig {
+        mint: Pubkey,
+        platform_fee: u64,
+        min_buy_in: u64,
+        max_liability_bps: u64,
+        enabled: bool,
+    }, // New token settings, same arguments as `set_mint_config`
+}
 
-        let hash = keccak::hash(&data);
-        u64::from_le_bytes(hash.to_bytes()[0..8].try_into().unwrap())
+// Accounts a change writes besides `PlinkoStatus`, with their bumps. Only the account the
+// change targets has to be passed
+#[derive(Default)]
+pub struct ChangeAccounts<'a> {
+    pub payout_table: Option<(&'a mut PayoutTable, u8)>,
+    pub fee_distribution: Option<(&'a mut FeeDistribution, u8)>,
+    pub fee_tiers: Option<(&'a mut FeeTiers, u8)>,
+    pub mint_config: Option<&'a mut MintConfig>,
+}
+
+impl ParameterChange {
+    pub const MAX_CHANGE_DELAY: i64 = 30 * 24 * 60 * 60; // 30 days
+
+    pub const LEN: usize =
+        1 + // variant
+        1 + // risk_level
+        1 + // rows
+        4 +
+        8 * PayoutTable::MAX_BUCKETS + // bucket_weights
+        4 +
+        8 * PayoutTable::MAX_BUCKETS; // payouts (largest variant)
+
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            ParameterChange::PlatformFee(fee) => {
+                require!(*fee <= 500, PlinkoError::PlatformFeeTooHigh); // Max 5%
+            }
+            ParameterChange::MinBuyIn(min_buy_in) => {
+                require!(*min_buy_in > 0, PlinkoError::InvalidValue);
+            }
+            ParameterChange::MaxBalls(max_balls) => {
+                require!(*max_balls <= Game::MAX_BALLS, PlinkoError::MaxBallsTooHigh);
+            }
+            ParameterChange::ChangeDelay(delay) => {
+                require!(
+                    (0..=ParameterChange::MAX_CHANGE_DELAY).contains(delay),
+                    PlinkoError::InvalidChangeDelay
+                );
+            }
+            ParameterChange::ReferralFee(fee) => {
+                require!(*fee <= 10_000, PlinkoError::InvalidReferralFee);
+            }
+            ParameterChange::MaxIntegratorFee(fee) => {
+                require!(*fee <= 500, PlinkoError::IntegratorFeeTooHigh); // Max 5%
+            }
+            ParameterChange::FeeTreasury(fee_treasury) => {
+                require!(*fee_treasury != Pubkey::default(), PlinkoError::InvalidFeeTreasury);
+            }
+            ParameterChange::FeeModel { winnings_fee, .. } => {
+                require!(*winnings_fee <= 1_000, PlinkoError::WinningsFeeTooHigh); // Max 10%
+            }
+            ParameterChange::Jackpot { jackpot_fee, jackpot_odds } => {
+                require!(*jackpot_fee <= 500, PlinkoError::InvalidJackpotConfig); // Max 5%
+                require!(*jackpot_fee == 0 || *jackpot_odds > 0, PlinkoError::InvalidJackpotConfig);
+            }
+            ParameterChange::Payout { rows, bucket_weights, payouts, .. } => {
+                PayoutTable::validate(*rows, bucket_weights, payouts)?;
+            }
+            ParameterChange::FeeDistribution(recipients) => {
+                FeeDistribution::validate(recipients)?;
+            }
+            ParameterChange::FeeTiers(tiers) => {
+                FeeTiers::validate(tiers)?;
+            }
+            ParameterChange::MintConfig { platform_fee, min_buy_in, max_liability_bps, .. } => {
+                MintConfig::validate(*platform_fee, *min_buy_in, *max_liability_bps)?;
+            }
+        }
+        Ok(())
+    }
+
+    // Role allowed to queue the change, `None` means owner only
+    pub fn required_role(&self) -> Option<Role> {
+        match self {
+            ParameterChange::PlatformFee(_) |
+            ParameterChange::MinBuyIn(_) |
+            ParameterChange::MaxBalls(_) |
+            ParameterChange::ReferralFee(_) |
+            ParameterChange::MaxIntegratorFee(_) |
+            ParameterChange::FeeModel { .. } |
+            ParameterChange::FeeTiers(_) |
+            ParameterChange::Jackpot { .. } |
+            ParameterChange::MintConfig { .. } => Some(Role::Operator),
+            ParameterChange::FeeTreasury(_) | ParameterChange::FeeDistribution(_) => {
+                Some(Role::Treasurer)
+            }
+            ParameterChange::Payout { .. } => Some(Role::OddsManager),
+            ParameterChange::ChangeDelay(_) => None,
+        }
+    }
+
+    pub fn apply(self, plinko_status: &mut PlinkoStatus, accounts: ChangeAccounts) -> Result<()> {
+        match self {
+            ParameterChange::PlatformFee(fee) => {
+                plinko_status.platform_fee = fee;
+            }
+            ParameterChange::MinBuyIn(min_buy_in) => {
+                plinko_status.min_buy_in = min_buy_in;
+            }
+            ParameterChange::MaxBalls(max_balls) => {
+                plinko_status.max_balls = max_balls;
+            }
+            ParameterChange::ChangeDelay(delay) => {
+                plinko_status.change_delay = delay;
+            }
+            ParameterChange::ReferralFee(fee) => {
+                plinko_status.referral_fee = fee;
+            }
+            ParameterChange::MaxIntegratorFee(fee) => {
+                plinko_status.max_integrator_fee = fee;
+            }
+            ParameterChange::FeeTreasury(fee_treasury) => {
+                plinko_status.fee_treasury = fee_treasury;
+            }
+            ParameterChange::FeeModel { fee_model, winnings_fee } => {
+                plinko_status.fee_model = fee_model;
+                plinko_status.winnings_fee = winnings_fee;
+            }
+            ParameterChange::Jackpot { jackpot_fee, jackpot_odds } => {
+                plinko_status.jackpot_fee = jackpot_fee;
+                plinko_status.jackpot_odds = jackpot_odds;
+            }
+            ParameterChange::Payout { risk_level, rows, bucket_weights, payouts } => {
+                // The odds may have been locked since the change was proposed
+                require!(!plinko_status.odds_locked, PlinkoError::OddsLocked);
+
+                let (payout_table, bump) = accounts.payout_table.ok_or(
+                    PlinkoError::PayoutTableNotConfigured
+                )?;
+                require!(!payout_table.locked, PlinkoError::PayoutTableLocked);
+
+                payout_table.configure(risk_level, rows, bucket_weights, payouts, bump);
+            }
+            ParameterChange::FeeDistribution(recipients) => {
+                let (fee_distribution, bump) = accounts.fee_distribution.ok_or(
+                    PlinkoError::MissingChangeAccount
+                )?;
+                fee_distribution.configure(recipients, bump);
+
+                // Platform fees collect in the fee vault from now on
+                plinko_status.fee_treasury = FeeDistribution::fee_vault();
+            }
+            ParameterChange::FeeTiers(tiers) => {
+                let (fee_tiers, bump) = accounts.fee_tiers.ok_or(
+                    PlinkoError::MissingChangeAccount
+                )?;
+                fee_tiers.configure(tiers, bump);
+            }
+            ParameterChange::MintConfig {
+                mint,
+                platform_fee,
+                min_buy_in,
+                max_liability_bps,
+                enabled,
+            } => {
+                let mint_config = accounts.mint_config.ok_or(PlinkoError::MissingChangeAccount)?;
+                require!(mint_config.mint == mint, PlinkoError::InvalidMint);
+                mint_config.configure(platform_fee, min_buy_in, max_liability_bps, enabled);
+            }
+        }
+        Ok(())
+    }
+
+    // Risk level and rows of the payout table a `Payout` change writes to
+    pub fn payout_table_key(&self) -> (u8, u8) {
+        match self {
+            ParameterChange::Payout { risk_level, rows, .. } => (*risk_level as u8, *rows),
+            _ => (0, 0),
+        }
+    }
+}
+
+#[account]
+pub struct PendingChange {
+    pub version: u8, // Layout version of the account
+    pub id: u64, // Id of the change, seed of the PDA
+    pub change: ParameterChange, // Change to apply
+    pub proposer: Pubkey, // Key that queued the change, gets the rent back
+    pub proposed_at: i64, // Timestamp when the change was queued
+    pub execute_after: i64, // Earliest timestamp the change can be executed
+    pub bump: u8, // PDA bump
+}
+
+impl PendingChange {
+    pub const LEN: usize =
+        8 + // discriminator
+        1 + // version
+        8 + // id
+        ParameterChange::LEN + // change
+        32 + // proposer
+        8 + // proposed_at
+        8 + // execute_after
+        1; // bump
+
+    // A raised delay also holds back changes queued before it, a lowered one never brings a
+    // change forward from the time players were shown
+    pub fn is_ready(&self, now: i64, change_delay: i64) -> bool {
+        now >= self.execute_after && now >= self.proposed_at.saturating_add(change_delay)
+    }
+}
+
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum MultisigAction {
+    Withdraw {
+        amount: u64,
+        recipient: Pubkey,
+    }, // Withdraw lamports from the SOL vault
+    LockOdds, // Lock all payout tables
+    Change(ParameterChange), // Fee, limit or payout change, queued if the change delay is on
+    AcceptOwner, // Accept a pending ownership transfer to the multisig
+    SetSigners {
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    }, // Replace the signer set, drops all open transactions
+    ProposeOwner {
+        new_owner: Pubkey,
+    }, // Propose handing ownership to another key
+    CancelOwnerTransfer, // Drop the pending ownership transfer
+    GrantRole {
+        member: Pubkey,
+        role: Role,
+    }, // Grant a role to a member
+    RevokeRole {
+        member: Pubkey,
+        role: Role,
+    }, // Revoke a role from a member
+    Migrate {
+        target: Pubkey,
+    }, // Migrate an account, run through `migrate`
+    EnableLiquidityPool {
+        seed_share_owner: Pubkey,
+    }, // Open the liquidity pool, run through `enable_liquidity_pool`
+}
+
+impl MultisigAction {
+    pub const LEN: usize =
+        1 + // variant
+        ParameterChange::LEN; // largest variant
+
+    pub fn payout_table_key(&self) -> (u8, u8) {
+        match self {
+            MultisigAction::Change(change) => change.payout_table_key(),
+            _ => (0, 0),
+        }
+    }
+}
+
+#[account]
+pub struct Multisig {
+    pub version: u8, // Layout version of the account
+    pub signers: Vec<Pubkey>, // Keys that can propose and approve transactions
+    pub threshold: u8, // Approvals needed to execute a transaction
+    pub seqno: u32, // Bumped when the signer set changes, invalidates open transactions
+    pub next_transaction_id: u64, // Id of the next transaction
+    pub bump: u8, // PDA bump
+}
+
+impl Multisig {
+    pub const MAX_SIGNERS: usize = 10;
+
+    pub const LEN: usize =
+        8 + // discriminator
+        1 + // version
+        4 +
+        32 * Multisig::MAX_SIGNERS + // signers
+        1 + // threshold
+        4 + // seqno
+        8 + // next_transaction_id
+        1; // bump
+
+    pub fn validate(signers: &[Pubkey], threshold: u8) -> Result<()> {
+        require!(
+            !signers.is_empty() && signers.len() <= Multisig::MAX_SIGNERS,
+            PlinkoError::InvalidMultisigSigners
+        );
+        for (i, signer) in signers.iter().enumerate() {
+            require!(!signers[..i].contains(signer), PlinkoError::InvalidMultisigSigners);
+        }
+        require!(
+            threshold > 0 && (threshold as usize) <= signers.len(),
+            PlinkoError::InvalidMultisigThreshold
+        );
+        Ok(())
+    }
+
+    pub fn signer_index(&self, key: &Pubkey) -> Result<usize> {
+        self.signers
+            .iter()
+            .position(|signer| signer == key)
+            .ok_or(PlinkoError::NotMultisigSigner.into())
+    }
+
+    pub fn check_approved(
+        &self,
+        transaction: &MultisigTransaction,
+        action: &MultisigAction
+    ) -> Result<()> {
+        require!(transaction.seqno == self.seqno, PlinkoError::StaleMultisigTransaction);
+        require!(
+            transaction.approval_count() >= (self.threshold as usize),
+            PlinkoError::NotEnoughApprovals
+        );
+        require!(transaction.action == *action, PlinkoError::MultisigActionMismatch);
+        Ok(())
+    }
+}
+
+#[account]
+pub struct MultisigTransaction {
+    pub version: u8, // Layout version of the account
+    pub id: u64, // Id of the transaction, seed of the PDA
+    pub action: MultisigAction, // Action to execute once approved
+    pub approvals: Vec<bool>, // Approval per signer, same order as `Multisig.signers`
+    pub seqno: u32, // Signer set the transaction was proposed under
+    pub proposer: Pubkey, // Signer that proposed the transaction, gets the rent back
+    pub created_at: i64, // Timestamp when the transaction was proposed
+    pub bump: u8, // PDA bump
+}
+
+impl MultisigTransaction {
+    pub const LEN: usize =
+        8 + // discriminator
+        1 + // version
+        8 + // id
+        MultisigAction::LEN + // action
+        4 +
+        Multisig::MAX_SIGNERS + // approvals
+        4 + // seqno
+        32 + // proposer
+        8 + // created_at
+        1; // bump
+
+    pub fn approval_count(&self) -> usize {
+        self.approvals
+            .iter()
+            .filter(|approved| **approved)
+            .count()
     }
 }
 
 #[account]
 pub struct Game {
-    pub game_id: u64, // Unique identifier for the game
+    pub version: u8, // Layout version of the account
+    pub nonce: u64, // Index of the game among the player's games
     pub player: Pubkey, // Player's public key
+    pub mint: Pubkey, // Mint the game is played in (default pubkey = SOL)
     pub bet_amount: u64, // Amount bet by the player
     pub amount_for_house: u64, // Amount allocated for the house
+    pub platform_fee: u64, // Platform fee charged on the bet
     pub num_balls: u8, // Number of balls played in the game
+    pub risk_level: RiskLevel, // Risk level of the payout table the game settles against
+    pub rows: u8, // Peg rows the balls dropped through (0 = weighted bucket mode)
     pub bet_amount_per_ball: u64, // Amount bet per ball
     pub buckets: Vec<u8>, // Buckets where balls landed
+    pub paths: Vec<u16>, // Left/right decisions per ball, bit `r` set = right at row `r`
     pub payout: u64, // Amount to be paid out to the player
-    pub has_ended: bool, // Whether the game has ended
+    pub max_payout: u64, // Worst-case payout reserved against the house at bet time
+    pub status: Status, // Settlement status of the game
     pub request_id: u64, // Request ID for VRF randomness
+    pub force: [u8; 32], // Seed of the VRF randomness request
+    pub random: Pubkey, // VRF randomness account requested for the game
     pub created_at: i64, // Timestamp when the game was created
+    pub expires_at: i64, // Timestamp after which the game can be refunded if still unfulfilled
     pub ended_at: i64, // Timestamp when the game ended
+    pub jackpot_contribution: u64, // Part of the bet added to the jackpot, makes the game eligible to win it
+    pub jackpot_payout: u64, // Jackpot paid to the player on top of the regular payout
+    pub referral_fee: u64, // Part of the platform fee paid to the player's referrer
+    pub fee_discount: u64, // Tier discount on the platform fee at bet time (10,000 = 100%)
+    pub rakeback: u64, // Rakeback earned by the bet, credited to the player once the game settles
 }
 
 impl Game {
+    pub const MAX_BALLS: u8 = 60; // Balls that fit in buckets and paths
+
     pub const LEN: usize =
         8 + // discriminator
-        8 + // game_id
+        1 + // version
+        8 + // nonce
         32 + // player
+        32 + // mint
         8 + // bet_amount
         8 + // amount_for_house
+        8 + // platform_fee
         1 + // num_balls
+        1 + // risk_level
+        1 + // rows
         8 + // bet_amount_per_ball
         4 +
-        60 * 1 + // buckets (max size of 100)
+        Game::MAX_BALLS as usize + // buckets
+        4 +
+        Game::MAX_BALLS as usize * 2 + // paths
         8 + // payout
-        1 + // has_ended
+        8 + // max_payout
+        1 + // status
         8 + // request_id
+        32 + // force
+        32 + // random
         8 + // created_at
-        8; // ended_at
+        8 + // expires_at
+        8 + // ended_at
+        8 + // jackpot_contribution
+        8 + // jackpot_payout
+        8 + // referral_fee
+        8 + // fee_discount
+        8; // rakeback
+
+    pub fn is_sol(&self) -> bool {
+        self.mint == Pubkey::default()
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.status == Status::Processing
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.is_pending() && now >= self.expires_at
+    }
+
+    pub fn check_closable(&self, closer: &Pubkey, now: i64, game_retention: i64) -> Result<()> {
+        require!(!self.is_pending(), PlinkoError::GameNotEnded);
+        // The player can close right away, anyone else has to wait for the retention period
+        require!(
+            *closer == self.player || now >= self.ended_at + game_retention,
+            PlinkoError::RetentionPeriodNotElapsed
+        );
+        Ok(())
+    }
+
+    // Splits a refunded platform fee into the treasury's share, paid back by the vault, and the
+    // referrer's share, taken back from its referral account as far as it is still unclaimed
+    pub fn platform_fee_refund(&self, referral: Option<&Referral>) -> Result<(u64, u64)> {
+        let from_referral = match referral {
+            Some(referral) => self.referral_fee.min(referral.unclaimed),
+            None => {
+                require!(self.referral_fee == 0, PlinkoError::InvalidReferral);
+                0
+            }
+        };
+        Ok((self.platform_fee - self.referral_fee, from_referral))
+    }
+
+    pub fn refund_amount(&self, refund_platform_fee: bool) -> Result<u64> {
+        if refund_platform_fee {
+            return self.amount_for_house
+                .checked_add(self.platform_fee)
+                .ok_or(PlinkoError::InvalidValue.into());
+        }
+        Ok(self.amount_for_house)
+    }
+
+    // Records the landing bucket (and peg path in board mode) of every ball, returns the total payout
+    pub fn drop_balls(
+        &mut self,
+        plinko_status: &PlinkoStatus,
+        payout_table: &PayoutTable,
+        randomness: u64
+    ) -> Result<u64> {
+        let mut total_payout = 0u64;
+        let mut buckets = Vec::new();
+
+        if self.rows == 0 {
+            let randoms: Vec<u16> = plinko_status.derive_many_randoms(
+                randomness,
+                self.num_balls.into()
+            );
+
+            for random in randoms.iter() {
+                buckets.push(payout_table.get_bucket_index(*random)?);
+            }
+        } else {
+            let paths: Vec<u16> = plinko_status.derive_many_paths(
+                randomness,
+                self.num_balls.into(),
+                self.rows
+            );
+
+            for path in paths.iter() {
+                buckets.push(payout_table.get_path_bucket_index(*path)?);
+            }
+            self.paths = paths;
+        }
+
+        for bucket_index in buckets.iter().copied() {
+            let ball_payout = payout_table.get_payout_amount(
+                self.bet_amount_per_ball,
+                bucket_index,
+                plinko_status.payout_denominator
+            )?;
+            total_payout = total_payout
+                .checked_add(ball_payout)
+                .ok_or(PlinkoError::InsufficientFunds)?;
+        }
+
+        self.buckets = buckets;
+        self.payout = total_payout;
+
+        Ok(total_payout)
+    }
 }
 
 #[account]
 pub struct UserStats {
+    pub version: u8, // Layout version of the account
     pub user: Pubkey, // User's public key
     pub total_games: u64, // Total number of games played by the user
     pub total_wagered: u64, // Total amount wagered by the user
     pub total_won: u64, // Total amount won by the user
-    pub game_ids: Vec<u64>, // List of game IDs played by the user
+    pub next_nonce: u64, // Nonce of the user's next game
+    pub recent_games: [GameRecord; UserStats::RECENT_GAMES], // Ring buffer of the latest games, slot = nonce % RECENT_GAMES
+    pub referrer: Pubkey, // Referral account the user is bound to (default pubkey = none)
+    pub rakeback_accrued: u64, // Rakeback earned and not claimed yet, in lamports
+    pub rakeback_claimed: u64, // Rakeback claimed so far, in lamports
 }
 
 impl UserStats {
+    pub const RECENT_GAMES: usize = 10;
+
     pub const LEN: usize =
         8 + // discriminator
+        1 + // version
         32 + // user
         8 + // total_games
         8 + // total_wagered
         8 + // total_won
+        8 + // next_nonce
+        GameRecord::LEN * UserStats::RECENT_GAMES + // recent_games
+        32 + // referrer
+        8 + // rakeback_accrued
+        8; // rakeback_claimed
+
+    pub fn initialize(&mut self, user: Pubkey) {
+        self.version = UserStats::VERSION;
+        self.user = user;
+        self.total_games = 0;
+        self.total_wagered = 0;
+        self.total_won = 0;
+        self.next_nonce = 0;
+        self.recent_games = [GameRecord::default(); UserStats::RECENT_GAMES];
+        self.referrer = Pubkey::default();
+        self.rakeback_accrued = 0;
+        self.rakeback_claimed = 0;
+    }
+
+    pub fn has_referrer(&self) -> bool {
+        self.referrer != Pubkey::default()
+    }
+
+    pub fn record_game(&mut self, game: &Game) {
+        self.recent_games[(game.nonce as usize) % UserStats::RECENT_GAMES] = GameRecord::from(game);
+    }
+
+    // A refunded or cancelled bet was never played, so it stops counting towards the fee tiers
+    pub fn remove_wager(&mut self, game: &Game) {
+        self.total_wagered = self.total_wagered.saturating_sub(game.bet_amount);
+    }
+}
+
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GameRecord {
+    pub nonce: u64, // Nonce of the game
+    pub mint: Pubkey, // Mint the game is played in (default pubkey = SOL)
+    pub bet_amount: u64, // Amount bet by the player
+    pub payout: u64, // Amount paid out to the player
+    pub status: Status, // Settlement status of the game
+    pub created_at: i64, // Timestamp when the game was created
+}
+
+impl GameRecord {
+    pub const LEN: usize =
+        8 + // nonce
+        32 + // mint
+        8 + // bet_amount
+        8 + // payout
+        1 + // status
+        8; // created_at
+}
+
+impl From<&Game> for GameRecord {
+    fn from(game: &Game) -> Self {
+        GameRecord {
+            nonce: game.nonce,
+            mint: game.mint,
+            bet_amount: game.bet_amount,
+            payout: game.payout,
+            status: game.status,
+            created_at: game.created_at,
+        }
+    }
+}
+
+#[account]
+pub struct HistoryPage {
+    pub version: u8, // Layout version of the account
+    pub player: Pubkey, // Player the page belongs to
+    pub page: u64, // Page number, holds nonces page * PAGE_SIZE..(page + 1) * PAGE_SIZE
+    pub records: Vec<GameRecord>, // Games of the page in nonce order
+    pub bump: u8, // Bump of the page PDA
+}
+
+impl HistoryPage {
+    pub const PAGE_SIZE: u64 = 50;
+
+    pub const LEN: usize =
+        8 + // discriminator
+        1 + // version
+        32 + // player
+        8 + // page
         4 +
-        100 * 8; // game_ids (max 100 games per user)
+        GameRecord::LEN * (HistoryPage::PAGE_SIZE as usize) + // records
+        1; // bump
+
+    pub fn page_of(nonce: u64) -> u64 {
+        nonce / HistoryPage::PAGE_SIZE
+    }
+
+    pub fn record_game(&mut self, game: &Game) -> Result<()> {
+        let index = (game.nonce % HistoryPage::PAGE_SIZE) as usize;
+
+        if index == self.records.len() {
+            self.records.push(GameRecord::from(game));
+        } else {
+            let record = self.records
+                .get_mut(index)
+                .ok_or(PlinkoError::InvalidHistoryPage)?;
+            *record = GameRecord::from(game);
+        }
+
+        Ok(())
+    }
+}
+
+#[account]
+pub struct Referral {
+    pub version: u8, // Layout version of the account
+    pub code: [u8; Referral::CODE_LEN], // Referral code, seed of the PDA
+    pub referrer: Pubkey, // Wallet that registered the code and claims its earnings
+    pub total_referred: u64, // Players bound to the code
+    pub total_volume: u64, // Lamports wagered by referred players
+    pub total_earned: u64, // Lamports earned from platform fees
+    pub unclaimed: u64, // Earnings held by this account and not claimed yet
+    pub bump: u8, // PDA bump
+}
+
+impl Referral {
+    pub const CODE_LEN: usize = 16;
+
+    pub const LEN: usize =
+        8 + // discriminator
+        1 + // version
+        Referral::CODE_LEN + // code
+        32 + // referrer
+        8 + // total_referred
+        8 + // total_volume
+        8 + // total_earned
+        8 + // unclaimed
+        1; // bump
+}
+
+#[account]
+pub struct IntegratorStats {
+    pub version: u8, // Layout version of the account
+    pub integrator: Pubkey, // Wallet receiving the integrator fees
+    pub total_games: u64, // Games placed through the integrator
+    pub total_volume: u64, // Lamports wagered through the integrator
+    pub total_fees: u64, // Lamports paid to the integrator
+    pub bump: u8, // PDA bump
+}
+
+impl IntegratorStats {
+    pub const LEN: usize =
+        8 + // discriminator
+        1 + // version
+        32 + // integrator
+        8 + // total_games
+        8 + // total_volume
+        8 + // total_fees
+        1; // bump
+}
+
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct FeeRecipient {
+    pub recipient: Pubkey, // Wallet receiving its share of the fees
+    pub bps: u64, // Share of the fees (10,000 = 100%)
+}
+
+impl FeeRecipient {
+    pub const LEN: usize =
+        32 + // recipient
+        8; // bps
+}
+
+#[account]
+pub struct FeeDistribution {
+    pub version: u8, // Layout version of the account
+    pub recipients: Vec<FeeRecipient>, // Recipients of the fee vault, shares sum to 10,000
+    pub total_distributed: u64, // Lamports paid out of the fee vault
+    pub bump: u8, // PDA bump
+}
+
+impl FeeDistribution {
+    pub const MAX_RECIPIENTS: usize = 8;
+    pub const TOTAL_BPS: u64 = 10_000;
+
+    pub const LEN: usize =
+        8 + // discriminator
+        1 + // version
+        4 +
+        FeeRecipient::LEN * FeeDistribution::MAX_RECIPIENTS + // recipients
+        8 + // total_distributed
+        1; // bump
+
+    pub fn fee_vault() -> Pubkey {
+        Pubkey::find_program_address(&[b"fee_vault"], &crate::ID).0
+    }
+
+    pub fn configure(&mut self, recipients: Vec<FeeRecipient>, bump: u8) {
+        if self.version == 0 {
+            self.version = FeeDistribution::VERSION;
+            self.total_distributed = 0;
+            self.bump = bump;
+        }
+        self.recipients = recipients;
+    }
+
+    pub fn validate(recipients: &[FeeRecipient]) -> Result<()> {
+        require!(
+            !recipients.is_empty() && recipients.len() <= FeeDistribution::MAX_RECIPIENTS,
+            PlinkoError::InvalidFeeRecipients
+        );
+
+        let mut total_bps: u64 = 0;
+        for (i, recipient) in recipients.iter().enumerate() {
+            require!(
+                recipient.recipient != Pubkey::default() &&
+                    recipient.bps > 0 &&
+                    !recipients[..i].iter().any(|other| other.recipient == recipient.recipient),
+                PlinkoError::InvalidFeeRecipients
+            );
+            total_bps += recipient.bps;
+        }
+        require!(total_bps == FeeDistribution::TOTAL_BPS, PlinkoError::InvalidFeeRecipients);
+
+        Ok(())
+    }
+
+    // Shares of `amount` in recipient order, rounding dust stays in the fee vault
+    pub fn shares(&self, amount: u64) -> Vec<u64> {
+        self.recipients
+            .iter()
+            .map(|recipient| {
+                (((amount as u128) * (recipient.bps as u128)) /
+                    (FeeDistribution::TOTAL_BPS as u128)) as u64
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct FeeTier {
+    pub min_wagered: u64, // Lamports a player must have wagered to reach the tier
+    pub fee_discount: u64, // Discount on the platform fee, up front and at settlement (10,000 = 100%)
+    pub rakeback: u64, // Share of each bet credited back to the player (10,000 = 100%)
+}
+
+impl FeeTier {
+    pub const LEN: usize =
+        8 + // min_wagered
+        8 + // fee_discount
+        8; // rakeback
+
+    pub const MAX_RAKEBACK: u64 = 500; // 5%
+
+    // Platform fee after the tier discount
+    pub fn discounted_fee(&self, platform_fee_amount: u64) -> u64 {
+        FeeTier::apply_discount(platform_fee_amount, self.fee_discount)
+    }
+
+    pub fn apply_discount(fee_amount: u64, fee_discount: u64) -> u64 {
+        let discount =
+            ((fee_amount as u128) * (fee_discount as u128)) / (FeeTiers::TOTAL_BPS as u128);
+        fee_amount - (discount as u64)
+    }
+
+    pub fn rakeback_amount(&self, total_bet: u64) -> u64 {
+        (((total_bet as u128) * (self.rakeback as u128)) / (FeeTiers::TOTAL_BPS as u128)) as u64
+    }
+}
+
+#[account]
+pub struct FeeTiers {
+    pub version: u8, // Layout version of the account
+    pub tiers: Vec<FeeTier>, // Tiers by ascending `min_wagered`
+    pub bump: u8, // PDA bump
+}
+
+impl FeeTiers {
+    pub const MAX_TIERS: usize = 10;
+    pub const TOTAL_BPS: u64 = 10_000;
+
+    pub const LEN: usize =
+        8 + // discriminator
+        1 + // version
+        4 +
+        FeeTier::LEN * FeeTiers::MAX_TIERS + // tiers
+        1; // bump
+
+    pub fn configure(&mut self, tiers: Vec<FeeTier>, bump: u8) {
+        if self.version == 0 {
+            self.version = FeeTiers::VERSION;
+            self.bump = bump;
+        }
+        self.tiers = tiers;
+    }
+
+    pub fn validate(tiers: &[FeeTier]) -> Result<()> {
+        require!(tiers.len() <= FeeTiers::MAX_TIERS, PlinkoError::InvalidFeeTiers);
+        for (i, tier) in tiers.iter().enumerate() {
+            require!(
+                tier.fee_discount <= FeeTiers::TOTAL_BPS && tier.rakeback <= FeeTier::MAX_RAKEBACK,
+                PlinkoError::InvalidFeeTiers
+            );
+            if i > 0 {
+                require!(
+                    tier.min_wagered > tiers[i - 1].min_wagered,
+                    PlinkoError::InvalidFeeTiers
+                );
+            }
+        }
+        Ok(())
+    }
+
+    // Highest tier the player has reached, `None` below the first threshold
+    pub fn tier_for(&self, total_wagered: u64) -> Option<&FeeTier> {
+        self.tiers
+            .iter()
+            .rev()
+            .find(|tier| total_wagered >= tier.min_wagered)
+    }
+}
+
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct JackpotHit {
+    pub player: Pubkey, // Player who won the jackpot
+    pub game: Pubkey, // Game that hit the jackpot
+    pub amount: u64, // Lamports paid out of the pool
+    pub timestamp: i64, // Timestamp of the hit
+}
+
+impl JackpotHit {
+    pub const LEN: usize =
+        32 + // player
+        32 + // game
+        8 + // amount
+        8; // timestamp
+}
+
+#[account]
+pub struct Jackpot {
+    pub version: u8, // Layout version of the account
+    pub balance: u64, // Lamports in the pool, paid in full on the next hit
+    pub total_contributed: u64, // Lamports contributed by all bets
+    pub total_paid: u64, // Lamports paid out to winners
+    pub total_hits: u64, // Number of times the jackpot was won
+    pub recent_hits: Vec<JackpotHit>, // Ring buffer of the latest hits, slot = hit index % MAX_HITS
+    pub bump: u8, // PDA bump
+}
+
+impl Jackpot {
+    pub const MAX_HITS: usize = 20;
+
+    pub const LEN: usize =
+        8 + // discriminator
+        1 + // version
+        8 + // balance
+        8 + // total_contributed
+        8 + // total_paid
+        8 + // total_hits
+        4 +
+        JackpotHit::LEN * Jackpot::MAX_HITS + // recent_hits
+        1; // bump
+
+    pub fn contribute(&mut self, amount: u64) {
+        self.balance += amount;
+        self.total_contributed += amount;
+    }
+
+    // Takes a refunded game's contribution back out of the pool, as far as the pool still holds it
+    pub fn withdraw_contribution(&mut self, amount: u64) -> u64 {
+        let amount = amount.min(self.balance);
+        self.balance -= amount;
+        self.total_contributed -= amount;
+        amount
+    }
+
+    // Empties the pool and returns the amount won
+    pub fn record_hit(&mut self, player: Pubkey, game: Pubkey, timestamp: i64) -> u64 {
+        let amount = self.balance;
+        let hit = JackpotHit { player, game, amount, timestamp };
+        let slot = (self.total_hits as usize) % Jackpot::MAX_HITS;
+        if slot < self.recent_hits.len() {
+            self.recent_hits[slot] = hit;
+        } else {
+            self.recent_hits.push(hit);
+        }
+
+        self.balance = 0;
+        self.total_paid += amount;
+        self.total_hits += 1;
+        amount
+    }
 }
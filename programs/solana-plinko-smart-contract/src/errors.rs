@@ -44,7 +44,7 @@ pub enum PlinkoError {
     #[msg("Cannot exceed 100% VRF fee")]
     VrfFeeTooHigh,
 
-    #[msg("Cannot exceed 100 balls")]
+    #[msg("Cannot exceed 60 balls")]
     MaxBallsTooHigh,
 
     #[msg("Invalid bucket index")]
@@ -61,4 +61,205 @@ pub enum PlinkoError {
 
     #[msg("Randomness is still being fulfilled")]
     StillProcessing,
+
+    #[msg("Invalid number of rows")]
+    InvalidRows,
+
+    #[msg("Payout table is locked")]
+    PayoutTableLocked,
+
+    #[msg("Payout table is not configured")]
+    PayoutTableNotConfigured,
+
+    #[msg("Invalid mint")]
+    InvalidMint,
+
+    #[msg("Bets in this mint are disabled")]
+    MintDisabled,
+
+    #[msg("Unsupported mint extension")]
+    UnsupportedMintExtension,
+
+    #[msg("Invalid fee treasury")]
+    InvalidFeeTreasury,
+
+    #[msg("Game has not expired yet")]
+    GameNotExpired,
+
+    #[msg("Randomness already fulfilled, settle the game instead")]
+    RandomnessFulfilled,
+
+    #[msg("Invalid refund timeout")]
+    InvalidRefundTimeout,
+
+    #[msg("Game does not belong to this player")]
+    InvalidPlayer,
+
+    #[msg("Bet would exceed the house exposure limit")]
+    ExposureLimitExceeded,
+
+    #[msg("Randomness account does not belong to this game")]
+    InvalidRandomnessAccount,
+
+    #[msg("Invalid history page")]
+    InvalidHistoryPage,
+
+    #[msg("Game has not ended yet")]
+    GameNotEnded,
+
+    #[msg("Retention period has not elapsed yet")]
+    RetentionPeriodNotElapsed,
+
+    #[msg("Invalid game retention")]
+    InvalidGameRetention,
+
+    #[msg("Account type cannot be migrated")]
+    UnknownAccount,
+
+    #[msg("Account already uses the latest layout")]
+    AccountAlreadyMigrated,
+
+    #[msg("Account layout version not supported")]
+    UnsupportedVersion,
+
+    #[msg("Invalid new owner")]
+    InvalidNewOwner,
+
+    #[msg("No ownership transfer in progress")]
+    NoPendingOwner,
+
+    #[msg("Only the pending owner can accept the transfer")]
+    OnlyPendingOwner,
+
+    #[msg("Signer does not hold the required role")]
+    MissingRole,
+
+    #[msg("Roles account is full")]
+    RolesFull,
+
+    #[msg("Role is not granted to this member")]
+    RoleNotGranted,
+
+    #[msg("Parameter changes are timelocked, queue them with propose_change")]
+    ChangeTimelocked,
+
+    #[msg("Invalid change delay")]
+    InvalidChangeDelay,
+
+    #[msg("Change delay has not elapsed yet")]
+    ChangeNotReady,
+
+    #[msg("Only the proposer or the owner can cancel this change")]
+    InvalidChangeCanceller,
+
+    #[msg("Invalid multisig signers")]
+    InvalidMultisigSigners,
+
+    #[msg("Invalid multisig threshold")]
+    InvalidMultisigThreshold,
+
+    #[msg("Signer is not part of the multisig")]
+    NotMultisigSigner,
+
+    #[msg("Multisig signers changed since the transaction was proposed")]
+    StaleMultisigTransaction,
+
+    #[msg("Not enough approvals to execute the transaction")]
+    NotEnoughApprovals,
+
+    #[msg("Multisig is not the owner")]
+    MultisigNotOwner,
+
+    #[msg("Missing account for this multisig action")]
+    MissingActionAccount,
+
+    #[msg("Withdrawals are paused")]
+    WithdrawalsPaused,
+
+    #[msg("Withdrawal exceeds the funds not reserved for rent and pending games")]
+    WithdrawalExceedsAvailable,
+
+    #[msg("Liquidity providers own the bankroll, use redeem_liquidity")]
+    LiquidityPoolActive,
+
+    #[msg("Liquidity pool is not enabled")]
+    LiquidityPoolNotEnabled,
+
+    #[msg("Invalid referral code")]
+    InvalidReferralCode,
+
+    #[msg("Referrer is already bound")]
+    ReferrerAlreadyBound,
+
+    #[msg("Players cannot refer themselves")]
+    SelfReferral,
+
+    #[msg("Referral account does not match the player's referrer")]
+    InvalidReferral,
+
+    #[msg("Referral fee cannot exceed 100% of the platform fee")]
+    InvalidReferralFee,
+
+    #[msg("Only the referrer can claim these earnings")]
+    OnlyReferrer,
+
+    #[msg("Nothing to claim")]
+    NothingToClaim,
+
+    #[msg("Integrator fee is above the configured maximum")]
+    IntegratorFeeTooHigh,
+
+    #[msg("Integrator accounts are missing or do not match")]
+    InvalidIntegrator,
+
+    #[msg("Fee recipients must be unique and their shares must sum to 10,000")]
+    InvalidFeeRecipients,
+
+    #[msg("Recipient accounts do not match the fee distribution")]
+    InvalidFeeRecipientAccount,
+
+    #[msg("Nothing to distribute")]
+    NothingToDistribute,
+
+    #[msg("Winnings fee too high")]
+    WinningsFeeTooHigh,
+
+    #[msg("Fee tiers must ascend by wagered amount with valid discounts and rakeback")]
+    InvalidFeeTiers,
+
+    #[msg("Jackpot fee too high or odds missing")]
+    InvalidJackpotConfig,
+
+    #[msg("Jackpot account required")]
+    MissingJackpot,
+
+    #[msg("Transaction does not approve this action")]
+    MultisigActionMismatch,
+
+    #[msg("This action runs through its own instruction")]
+    ActionHasOwnInstruction,
+
+    #[msg("Only the proposer can run this transaction")]
+    OnlyTransactionProposer,
+
+    #[msg("Missing account for this parameter change")]
+    MissingChangeAccount,
+
+    #[msg("Payout table account required")]
+    MissingPayoutTable,
+
+    #[msg("Pending version 1 game, close it with close_legacy_game")]
+    LegacyGamePending,
+
+    #[msg("Liquidity pool has no assets backing its shares")]
+    EmptyLiquidityPool,
+
+    #[msg("Games are pending, try again once they settle")]
+    GamesPending,
+
+    #[msg("No fee refunds to repay")]
+    NothingToRepay,
+
+    #[msg("Only the proposer or a multisig signer can cancel this transaction")]
+    InvalidTransactionCanceller,
 }
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+// Lets indexers enumerate games without scanning every player's nonces
+#[event]
+pub struct GameCreated {
+    pub game: Pubkey,
+    pub player: Pubkey,
+    pub nonce: u64,
+    pub mint: Pubkey,
+}
+
+// Keeps the full jackpot history available beyond the hits stored on-chain
+#[event]
+pub struct JackpotWon {
+    pub game: Pubkey,
+    pub player: Pubkey,
+    pub amount: u64,
+    pub hit: u64, // Number of hits so far, including this one
+}
@@ -0,0 +1,40 @@
+use crate::account::*;
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AcceptOwner<'info> {
+    #[account(
+        mut,
+        seeds = [b"plinko_status"],
+        bump,
+        constraint = plinko_status.pending_owner != Pubkey::default() @ PlinkoError::NoPendingOwner,
+        constraint = plinko_status.pending_owner == new_owner.key() @ PlinkoError::OnlyPendingOwner
+    )]
+    pub plinko_status: Account<'info, PlinkoStatus>,
+
+    #[account(
+        mut,
+        seeds = [b"house"],
+        bump
+    )]
+    pub house: Account<'info, House>,
+
+    pub new_owner: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<AcceptOwner>) -> Result<()> {
+    let plinko_status = &mut ctx.accounts.plinko_status;
+    let house = &mut ctx.accounts.house;
+    let previous_owner = plinko_status.owner;
+
+    plinko_status.owner = ctx.accounts.new_owner.key();
+    plinko_status.pending_owner = Pubkey::default();
+    house.owner = ctx.accounts.new_owner.key();
+
+    msg!("Ownership transferred");
+    msg!("Previous owner: {}", previous_owner);
+    msg!("New owner: {}", plinko_status.owner);
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use crate::account::*;
+use crate::errors::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{ Mint, TokenAccount, TokenInterface };
+
+#[derive(Accounts)]
+pub struct AddMint<'info> {
+    #[account(
+        seeds = [b"plinko_status"],
+        bump,
+        constraint = plinko_status.has_role(
+            &authority.key(),
+            roles.as_deref(),
+            Role::Operator
+        ) @ PlinkoError::MissingRole
+    )]
+    pub plinko_status: Account<'info, PlinkoStatus>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + MintConfig::LEN,
+        seeds = [b"mint_config", mint.key().as_ref()],
+        bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account(seeds = [b"vaultseed"], bump)]
+    pub vault: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"token_vault", mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vault,
+        token::token_program = token_program
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AddMint>, platform_fee: u64, min_buy_in: u64) -> Result<()> {
+    require!(platform_fee <= 500, PlinkoError::PlatformFeeTooHigh); // Max 5%
+    require!(min_buy_in > 0, PlinkoError::InvalidValue);
+
+    check_mint_extensions(&ctx.accounts.mint.to_account_info())?;
+
+    let mint_config = &mut ctx.accounts.mint_config;
+
+    mint_config.version = MintConfig::VERSION;
+    mint_config.mint = ctx.accounts.mint.key();
+    mint_config.token_program = ctx.accounts.token_program.key();
+    mint_config.decimals = ctx.accounts.mint.decimals;
+    mint_config.platform_fee = platform_fee;
+    mint_config.min_buy_in = min_buy_in;
+    mint_config.enabled = true;
+    mint_config.balance = 0;
+    mint_config.total_games = 0;
+    mint_config.total_volume = 0;
+    mint_config.total_payouts = 0;
+    mint_config.pending_request = 0;
+    mint_config.max_liability_bps = 10_000;
+    mint_config.pending_liability = 0;
+    mint_config.bump = ctx.bumps.mint_config;
+    mint_config.total_distributed = 0;
+    mint_config.vault_bump = ctx.bumps.vault_token_account;
+
+    msg!("Mint added successfully");
+    msg!("Mint: {}", mint_config.mint);
+    msg!("Vault: {}", ctx.accounts.vault_token_account.key());
+    msg!("Platform Fee: {} basis points", platform_fee);
+    msg!("Min Buy-in: {}", min_buy_in);
+
+    Ok(())
+}
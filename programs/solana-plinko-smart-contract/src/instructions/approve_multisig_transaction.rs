@@ -0,0 +1,35 @@
+use crate::account::*;
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct ApproveMultisigTransaction<'info> {
+    #[account(seeds = [b"multisig"], bump = multisig.bump)]
+    pub multisig: Box<Account<'info, Multisig>>,
+
+    #[account(
+        mut,
+        seeds = [b"multisig_tx", id.to_le_bytes().as_ref()],
+        bump = transaction.bump,
+        constraint = transaction.seqno == multisig.seqno @ PlinkoError::StaleMultisigTransaction
+    )]
+    pub transaction: Box<Account<'info, MultisigTransaction>>,
+
+    pub signer: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ApproveMultisigTransaction>, id: u64) -> Result<()> {
+    let multisig = &ctx.accounts.multisig;
+    let signer_index = multisig.signer_index(&ctx.accounts.signer.key())?;
+
+    let transaction = &mut ctx.accounts.transaction;
+    transaction.approvals[signer_index] = true;
+
+    msg!("Multisig transaction approved");
+    msg!("Transaction id: {}", id);
+    msg!("Signer: {}", ctx.accounts.signer.key());
+    msg!("Approvals: {}/{}", transaction.approval_count(), multisig.threshold);
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use crate::account::*;
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct BindReferrer<'info> {
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + UserStats::LEN,
+        seeds = [b"user_stats", player.key().as_ref()],
+        bump,
+        constraint = !user_stats.has_referrer() @ PlinkoError::ReferrerAlreadyBound
+    )]
+    pub user_stats: Box<Account<'info, UserStats>>,
+
+    #[account(
+        mut,
+        seeds = [b"referral", referral.code.as_ref()],
+        bump = referral.bump,
+        constraint = referral.referrer != player.key() @ PlinkoError::SelfReferral
+    )]
+    pub referral: Account<'info, Referral>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<BindReferrer>) -> Result<()> {
+    let user_stats = &mut ctx.accounts.user_stats;
+    if user_stats.user == Pubkey::default() {
+        user_stats.initialize(ctx.accounts.player.key());
+    }
+
+    let referral = &mut ctx.accounts.referral;
+    user_stats.referrer = referral.key();
+    referral.total_referred += 1;
+
+    msg!("Referrer bound");
+    msg!("Player: {}", user_stats.user);
+    msg!("Referral: {}", referral.key());
+
+    Ok(())
+}
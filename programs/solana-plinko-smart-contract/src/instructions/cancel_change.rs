@@ -0,0 +1,35 @@
+use crate::account::*;
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct CancelChange<'info> {
+    #[account(seeds = [b"plinko_status"], bump)]
+    pub plinko_status: Account<'info, PlinkoStatus>,
+
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [b"change", id.to_le_bytes().as_ref()],
+        bump = pending_change.bump,
+        constraint = pending_change.proposer == authority.key() ||
+            plinko_status.is_owner(&authority.key()) @ PlinkoError::InvalidChangeCanceller
+    )]
+    pub pending_change: Box<Account<'info, PendingChange>>,
+
+    /// CHECK: This account is the proposer of the change, receives the rent back
+    #[account(mut, address = pending_change.proposer)]
+    pub proposer: AccountInfo<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CancelChange>, id: u64) -> Result<()> {
+    msg!("Parameter change cancelled");
+    msg!("Change id: {}", id);
+    msg!("Change: {:?}", ctx.accounts.pending_change.change);
+    msg!("Cancelled by: {}", ctx.accounts.authority.key());
+
+    Ok(())
+}
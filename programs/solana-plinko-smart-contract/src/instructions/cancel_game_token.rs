@@ -0,0 +1,183 @@
+use crate::account::*;
+use crate::errors::*;
+use crate::misc::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{ Mint, TokenAccount, TokenInterface };
+use orao_solana_vrf::RANDOMNESS_ACCOUNT_SEED;
+
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct CancelGameToken<'info> {
+    #[account(
+        seeds = [b"plinko_status"],
+        bump,
+        constraint = plinko_status.has_role(
+            &authority.key(),
+            roles.as_deref(),
+            Role::Operator
+        ) @ PlinkoError::MissingRole
+    )]
+    pub plinko_status: Box<Account<'info, PlinkoStatus>>,
+
+    #[account(
+        mut,
+        seeds = [b"game", player.key().as_ref(), nonce.to_le_bytes().as_ref()],
+        bump,
+        has_one = player @ PlinkoError::InvalidPlayer,
+        constraint = game.is_pending() @ PlinkoError::GameAlreadyEnded,
+        constraint = game.mint == mint.key() @ PlinkoError::InvalidMint
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    #[account(mut, seeds = [b"user_stats", game.player.key().as_ref()], bump)]
+    pub user_stats: Box<Account<'info, UserStats>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"history",
+            game.player.as_ref(),
+            HistoryPage::page_of(game.nonce).to_le_bytes().as_ref(),
+        ],
+        bump = history_page.bump
+    )]
+    pub history_page: Box<Account<'info, HistoryPage>>,
+
+    #[account(
+        mut,
+        seeds = [b"mint_config", mint.key().as_ref()],
+        bump = mint_config.bump
+    )]
+    pub mint_config: Box<Account<'info, MintConfig>>,
+
+    #[account(mut, mint::token_program = token_program)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account(seeds = [b"vaultseed"], bump)]
+    pub vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"token_vault", mint.key().as_ref()],
+        bump = mint_config.vault_bump
+    )]
+    pub vault_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", game.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vault,
+        token::token_program = token_program
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Randomness
+    #[account(
+        address = game.random @ PlinkoError::InvalidRandomnessAccount,
+        seeds = [RANDOMNESS_ACCOUNT_SEED, &game.force],
+        bump,
+        seeds::program = orao_solana_vrf::ID
+    )]
+    pub random: AccountInfo<'info>,
+
+    /// CHECK: This account is the player who played the game
+    #[account(mut)]
+    pub player: AccountInfo<'info>,
+
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = player,
+        token::token_program = token_program
+    )]
+    pub player_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<CancelGameToken>, _nonce: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    // Once the randomness is public the outcome is known, so only settlement is allowed
+    let rand_acc = get_account_data(&ctx.accounts.random)?;
+    require!(current_state(&rand_acc) == 0, PlinkoError::RandomnessFulfilled);
+
+    // The house is at fault, so the platform fee is always returned too
+    let refund = ctx.accounts.game.refund_amount(true)?;
+
+    // The stake comes back from escrow, only a refunded platform fee is paid by the vault
+    let staked = ctx.accounts.escrow_token_account.amount;
+    let from_vault = refund.saturating_sub(staked);
+    require!(
+        ctx.accounts.vault_token_account.amount >= from_vault,
+        PlinkoError::InsufficientFunds
+    );
+
+    let vault_bump = ctx.bumps.vault;
+    let vault_signer: &[&[&[u8]]; 1] = &[&[b"vaultseed", &[vault_bump]]];
+    let decimals = ctx.accounts.mint.decimals;
+
+    if staked > 0 {
+        token_transfer_with_signer(
+            ctx.accounts.escrow_token_account.to_account_info(),
+            ctx.accounts.player_token_account.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            vault_signer,
+            staked,
+            decimals
+        )?;
+    }
+    if from_vault > 0 {
+        token_transfer_with_signer(
+            ctx.accounts.vault_token_account.to_account_info(),
+            ctx.accounts.player_token_account.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            vault_signer,
+            from_vault,
+            decimals
+        )?;
+    }
+
+    token_close_with_signer(
+        ctx.accounts.escrow_token_account.to_account_info(),
+        ctx.accounts.player.to_account_info(),
+        ctx.accounts.vault.to_account_info(),
+        ctx.accounts.mint.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        vault_signer
+    )?;
+    ctx.accounts.vault_token_account.reload()?;
+
+    let mint_config = &mut ctx.accounts.mint_config;
+    mint_config.balance = ctx.accounts.vault_token_account.amount;
+    mint_config.pending_request = mint_config.pending_request.saturating_sub(1);
+    mint_config.release_liability(ctx.accounts.game.max_payout);
+
+    let game = &mut ctx.accounts.game;
+    game.status = Status::Cancelled;
+    game.ended_at = now;
+
+    ctx.accounts.user_stats.record_game(game);
+    ctx.accounts.history_page.record_game(game)?;
+
+    msg!("Game cancelled");
+    msg!("Game: {}", game.key());
+    msg!("Player: {}", game.player);
+    msg!("Mint: {}", game.mint);
+    msg!("Refund: {}", refund);
+
+    Ok(())
+}
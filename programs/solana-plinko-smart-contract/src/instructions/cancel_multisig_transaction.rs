@@ -0,0 +1,36 @@
+use crate::account::*;
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct CancelMultisigTransaction<'info> {
+    #[account(seeds = [b"multisig"], bump = multisig.bump)]
+    pub multisig: Box<Account<'info, Multisig>>,
+
+    // Stale transactions can be cancelled too, that is how their rent comes back
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [b"multisig_tx", id.to_le_bytes().as_ref()],
+        bump = transaction.bump,
+        constraint = transaction.proposer == authority.key() ||
+            multisig.signers.contains(&authority.key()) @ PlinkoError::InvalidTransactionCanceller
+    )]
+    pub transaction: Box<Account<'info, MultisigTransaction>>,
+
+    /// CHECK: This account is the proposer of the transaction, receives the rent back
+    #[account(mut, address = transaction.proposer)]
+    pub proposer: AccountInfo<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CancelMultisigTransaction>, id: u64) -> Result<()> {
+    msg!("Multisig transaction cancelled");
+    msg!("Transaction id: {}", id);
+    msg!("Action: {:?}", ctx.accounts.transaction.action);
+    msg!("Cancelled by: {}", ctx.accounts.authority.key());
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use crate::account::*;
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CancelOwnerTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [b"plinko_status"],
+        bump,
+        constraint = plinko_status.is_owner(&authority.key()) @ PlinkoError::OnlyOwner,
+        constraint = plinko_status.pending_owner != Pubkey::default() @ PlinkoError::NoPendingOwner
+    )]
+    pub plinko_status: Account<'info, PlinkoStatus>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CancelOwnerTransfer>) -> Result<()> {
+    let plinko_status = &mut ctx.accounts.plinko_status;
+    let cancelled_owner = plinko_status.pending_owner;
+
+    plinko_status.pending_owner = Pubkey::default();
+
+    msg!("Ownership transfer cancelled");
+    msg!("Cancelled pending owner: {}", cancelled_owner);
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use crate::account::*;
+use crate::errors::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ClaimRakeback<'info> {
+    #[account(mut, seeds = [b"user_stats", player.key().as_ref()], bump)]
+    pub user_stats: Box<Account<'info, UserStats>>,
+
+    #[account(
+        mut,
+        seeds = [b"house"],
+        bump
+    )]
+    pub house: Account<'info, House>,
+
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account(mut, seeds = [b"vaultseed"], bump)]
+    pub vault: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ClaimRakeback>) -> Result<()> {
+    let amount = ctx.accounts.user_stats.rakeback_accrued;
+    require!(amount > 0, PlinkoError::NothingToClaim);
+    require!(ctx.accounts.vault.lamports() >= amount, PlinkoError::InsufficientFunds);
+
+    let vault_bump = ctx.bumps.vault;
+    sol_transfer_with_signer(
+        ctx.accounts.vault.to_account_info(),
+        ctx.accounts.player.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+        &[&[b"vaultseed", &[vault_bump]]],
+        amount
+    )?;
+
+    let user_stats = &mut ctx.accounts.user_stats;
+    user_stats.rakeback_accrued = 0;
+    user_stats.rakeback_claimed += amount;
+
+    let house = &mut ctx.accounts.house;
+    house.unclaimed_rakeback = house.unclaimed_rakeback.saturating_sub(amount);
+    house.balance = ctx.accounts.vault.lamports();
+
+    msg!("Rakeback claimed");
+    msg!("Player: {}", ctx.accounts.player.key());
+    msg!("Amount: {} lamports", amount);
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use crate::account::*;
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ClaimReferralEarnings<'info> {
+    #[account(
+        mut,
+        seeds = [b"referral", referral.code.as_ref()],
+        bump = referral.bump,
+        has_one = referrer @ PlinkoError::OnlyReferrer
+    )]
+    pub referral: Account<'info, Referral>,
+
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ClaimReferralEarnings>) -> Result<()> {
+    let amount = ctx.accounts.referral.unclaimed;
+    require!(amount > 0, PlinkoError::NothingToClaim);
+
+    // Earnings are held as lamports on the referral account itself, above its rent
+    ctx.accounts.referral.sub_lamports(amount)?;
+    ctx.accounts.referrer.add_lamports(amount)?;
+    ctx.accounts.referral.unclaimed = 0;
+
+    msg!("Referral earnings claimed");
+    msg!("Referral: {}", ctx.accounts.referral.key());
+    msg!("Amount: {} lamports", amount);
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use crate::account::*;
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct CloseGame<'info> {
+    #[account(seeds = [b"plinko_status"], bump)]
+    pub plinko_status: Account<'info, PlinkoStatus>,
+
+    #[account(
+        mut,
+        close = player,
+        seeds = [b"game", player.key().as_ref(), nonce.to_le_bytes().as_ref()],
+        bump,
+        has_one = player @ PlinkoError::InvalidPlayer
+    )]
+    pub game: Account<'info, Game>,
+
+    /// CHECK: This account is the player who paid the game rent
+    #[account(mut)]
+    pub player: AccountInfo<'info>,
+
+    pub closer: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CloseGame>, _nonce: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let game = &ctx.accounts.game;
+    game.check_closable(&ctx.accounts.closer.key(), now, ctx.accounts.plinko_status.game_retention)?;
+
+    msg!("Game closed");
+    msg!("Game: {}", game.key());
+    msg!("Player: {}", game.player);
+    msg!("Closed by: {}", ctx.accounts.closer.key());
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use crate::account::*;
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CloseGames<'info> {
+    #[account(seeds = [b"plinko_status"], bump)]
+    pub plinko_status: Account<'info, PlinkoStatus>,
+
+    /// CHECK: This account is the player who paid the game rent
+    #[account(mut)]
+    pub player: AccountInfo<'info>,
+
+    pub closer: Signer<'info>,
+}
+
+// Closes every game passed in the remaining accounts, all of them must belong to `player`
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CloseGames<'info>>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let player = &ctx.accounts.player;
+    let closer = ctx.accounts.closer.key();
+    let game_retention = ctx.accounts.plinko_status.game_retention;
+
+    require!(!ctx.remaining_accounts.is_empty(), PlinkoError::InvalidValue);
+
+    for game_info in ctx.remaining_accounts.iter() {
+        let game = Account::<Game>::try_from(game_info)?;
+
+        let (game_pda, _) = Pubkey::find_program_address(
+            &[b"game", player.key().as_ref(), game.nonce.to_le_bytes().as_ref()],
+            ctx.program_id
+        );
+        require_keys_eq!(game_pda, game_info.key(), PlinkoError::InvalidPlayer);
+        require_keys_eq!(game.player, player.key(), PlinkoError::InvalidPlayer);
+        game.check_closable(&closer, now, game_retention)?;
+
+        game.close(player.to_account_info())?;
+        msg!("Game closed: {}", game_info.key());
+    }
+
+    msg!("Closed {} games", ctx.remaining_accounts.len());
+    msg!("Player: {}", player.key());
+    msg!("Closed by: {}", closer);
+
+    Ok(())
+}
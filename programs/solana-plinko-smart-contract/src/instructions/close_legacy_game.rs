@@ -0,0 +1,87 @@
+use crate::account::*;
+use crate::errors::*;
+use crate::legacy::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct CloseLegacyGame<'info> {
+    #[account(seeds = [b"plinko_status"], bump)]
+    pub plinko_status: Account<'info, PlinkoStatus>,
+
+    /// CHECK: Version 1 game, read by hand since `migrate` does not upgrade pending ones
+    #[account(mut, seeds = [b"game", game_id.to_le_bytes().as_ref()], bump, owner = crate::ID)]
+    pub game: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"house"],
+        bump
+    )]
+    pub house: Account<'info, House>,
+
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account(mut, seeds = [b"vaultseed"], bump)]
+    pub vault: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CloseLegacyGame>, _game_id: u64) -> Result<()> {
+    let game = {
+        let data = ctx.accounts.game.try_borrow_data()?;
+        require!(
+            data.len() == GameV1::SPACE && data[..8] == *Game::DISCRIMINATOR,
+            PlinkoError::UnknownAccount
+        );
+        GameV1::deserialize(&mut &data[8..])?
+    };
+    require!(game.player == ctx.accounts.player.key(), PlinkoError::InvalidPlayer);
+
+    // The randomness of a version 1 game was tracked on `plinko_status` and is gone, so a
+    // pending game can no longer settle. Once the refund timeout passes its stake comes back.
+    let mut refund = 0;
+    if !game.has_ended {
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now >= game.created_at + ctx.accounts.plinko_status.refund_timeout,
+            PlinkoError::GameNotExpired
+        );
+
+        refund = game.amount_for_house;
+        require!(ctx.accounts.vault.lamports() >= refund, PlinkoError::InsufficientFunds);
+
+        let vault_bump = ctx.bumps.vault;
+        sol_transfer_with_signer(
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.player.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            &[&[b"vaultseed", &[vault_bump]]],
+            refund
+        )?;
+
+        let house = &mut ctx.accounts.house;
+        house.balance = ctx.accounts.vault.lamports();
+        house.pending_request = house.pending_request.saturating_sub(1);
+    }
+
+    // Close the game, its rent goes back to the player who paid it
+    let game_account = &ctx.accounts.game;
+    let rent = game_account.lamports();
+    game_account.sub_lamports(rent)?;
+    ctx.accounts.player.add_lamports(rent)?;
+    game_account.assign(&System::id());
+    game_account.resize(0)?;
+
+    msg!("Legacy game closed");
+    msg!("Game ID: {}", game.game_id);
+    msg!("Player: {}", game.player);
+    msg!("Refund: {} lamports", refund);
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use crate::account::*;
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CreateJackpot<'info> {
+    #[account(
+        seeds = [b"plinko_status"],
+        bump,
+        constraint = plinko_status.has_role(
+            &authority.key(),
+            roles.as_deref(),
+            Role::Operator
+        ) @ PlinkoError::MissingRole
+    )]
+    pub plinko_status: Account<'info, PlinkoStatus>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Jackpot::LEN,
+        seeds = [b"jackpot"],
+        bump
+    )]
+    pub jackpot: Box<Account<'info, Jackpot>>,
+
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateJackpot>) -> Result<()> {
+    let jackpot = &mut ctx.accounts.jackpot;
+    jackpot.version = Jackpot::VERSION;
+    jackpot.balance = 0;
+    jackpot.total_contributed = 0;
+    jackpot.total_paid = 0;
+    jackpot.total_hits = 0;
+    jackpot.recent_hits = Vec::new();
+    jackpot.bump = ctx.bumps.jackpot;
+
+    msg!("Jackpot created");
+    msg!("Jackpot: {}", jackpot.key());
+
+    Ok(())
+}
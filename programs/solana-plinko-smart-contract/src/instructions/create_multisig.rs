@@ -0,0 +1,46 @@
+use crate::account::*;
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CreateMultisig<'info> {
+    #[account(
+        seeds = [b"plinko_status"],
+        bump,
+        constraint = plinko_status.is_owner(&authority.key()) @ PlinkoError::OnlyOwner
+    )]
+    pub plinko_status: Account<'info, PlinkoStatus>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Multisig::LEN,
+        seeds = [b"multisig"],
+        bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateMultisig>, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+    Multisig::validate(&signers, threshold)?;
+
+    let multisig = &mut ctx.accounts.multisig;
+    multisig.version = Multisig::VERSION;
+    multisig.signers = signers;
+    multisig.threshold = threshold;
+    multisig.seqno = 0;
+    multisig.next_transaction_id = 0;
+    multisig.bump = ctx.bumps.multisig;
+
+    // The multisig only gains power once it accepts ownership through `propose_owner`
+    msg!("Multisig created: {}", multisig.key());
+    msg!("Signers: {:?}", multisig.signers);
+    msg!("Threshold: {}", threshold);
+
+    Ok(())
+}
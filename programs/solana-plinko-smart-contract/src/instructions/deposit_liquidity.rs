@@ -0,0 +1,87 @@
+use crate::account::*;
+use crate::errors::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{ Mint, TokenAccount, TokenInterface };
+
+#[derive(Accounts)]
+pub struct DepositLiquidity<'info> {
+    #[account(
+        mut,
+        seeds = [b"house"],
+        bump,
+        constraint = house.lp_enabled @ PlinkoError::LiquidityPoolNotEnabled,
+        constraint = house.pending_liability == 0 @ PlinkoError::GamesPending
+    )]
+    pub house: Box<Account<'info, House>>,
+
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account(mut, seeds = [b"vaultseed"], bump)]
+    pub vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"lp_mint"],
+        bump,
+        mint::token_program = token_program
+    )]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        associated_token::mint = lp_mint,
+        associated_token::authority = depositor,
+        associated_token::token_program = token_program
+    )]
+    pub depositor_share_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<DepositLiquidity>, amount: u64) -> Result<()> {
+    require!(amount > 0, PlinkoError::InvalidValue);
+
+    let net_asset_value = ctx.accounts.house.net_asset_value(ctx.accounts.vault.lamports())?;
+    let shares = House::shares_for_deposit(amount, ctx.accounts.lp_mint.supply, net_asset_value)?;
+    require!(shares > 0, PlinkoError::InvalidValue);
+
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.depositor.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            }
+        ),
+        amount
+    )?;
+
+    let vault_bump = ctx.bumps.vault;
+    token_mint_to_with_signer(
+        ctx.accounts.lp_mint.to_account_info(),
+        ctx.accounts.depositor_share_account.to_account_info(),
+        ctx.accounts.vault.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        &[&[b"vaultseed", &[vault_bump]]],
+        shares
+    )?;
+
+    let house = &mut ctx.accounts.house;
+    house.balance = ctx.accounts.vault.lamports();
+
+    msg!("Liquidity deposited");
+    msg!("Depositor: {}", ctx.accounts.depositor.key());
+    msg!("Amount: {} lamports", amount);
+    msg!("Shares minted: {}", shares);
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use crate::account::*;
+use crate::errors::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct DistributeFees<'info> {
+    #[account(
+        mut,
+        seeds = [b"fee_distribution"],
+        bump = fee_distribution.bump
+    )]
+    pub fee_distribution: Account<'info, FeeDistribution>,
+
+    /// CHECK: Collects platform fees until they are distributed
+    #[account(mut, seeds = [b"fee_vault"], bump)]
+    pub fee_vault: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Pays out the fee vault above its rent, the recipient wallets are passed as remaining
+// accounts in the order of the fee distribution
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, DistributeFees<'info>>) -> Result<()> {
+    let fee_distribution = &mut ctx.accounts.fee_distribution;
+    require!(
+        ctx.remaining_accounts.len() == fee_distribution.recipients.len(),
+        PlinkoError::InvalidFeeRecipientAccount
+    );
+
+    let rent = Rent::get()?.minimum_balance(0);
+    let available = ctx.accounts.fee_vault.lamports().saturating_sub(rent);
+    require!(available > 0, PlinkoError::NothingToDistribute);
+
+    let fee_vault_bump = ctx.bumps.fee_vault;
+    let shares = fee_distribution.shares(available);
+    let mut distributed = 0;
+
+    for ((recipient, recipient_info), share) in fee_distribution.recipients
+        .iter()
+        .zip(ctx.remaining_accounts.iter())
+        .zip(shares) {
+        require_keys_eq!(
+            recipient_info.key(),
+            recipient.recipient,
+            PlinkoError::InvalidFeeRecipientAccount
+        );
+
+        if share > 0 {
+            sol_transfer_with_signer(
+                ctx.accounts.fee_vault.to_account_info(),
+                recipient_info.clone(),
+                ctx.accounts.system_program.to_account_info(),
+                &[&[b"fee_vault", &[fee_vault_bump]]],
+                share
+            )?;
+        }
+        distributed += share;
+        msg!("Paid {} lamports to {}", share, recipient.recipient);
+    }
+
+    fee_distribution.total_distributed += distributed;
+
+    msg!("Fees distributed");
+    msg!("Total: {} lamports", distributed);
+
+    Ok(())
+}
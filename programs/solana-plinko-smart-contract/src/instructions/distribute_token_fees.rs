@@ -0,0 +1,86 @@
+use crate::account::*;
+use crate::errors::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{ Mint, TokenAccount, TokenInterface };
+
+#[derive(Accounts)]
+pub struct DistributeTokenFees<'info> {
+    #[account(seeds = [b"fee_distribution"], bump = fee_distribution.bump)]
+    pub fee_distribution: Account<'info, FeeDistribution>,
+
+    /// CHECK: Owns the fee token accounts, signs the transfers
+    #[account(seeds = [b"fee_vault"], bump)]
+    pub fee_vault: AccountInfo<'info>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, seeds = [b"mint_config", mint.key().as_ref()], bump = mint_config.bump)]
+    pub mint_config: Box<Account<'info, MintConfig>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = fee_vault,
+        token::token_program = token_program
+    )]
+    pub fee_vault_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Pays out a fee vault token account, the recipients' token accounts of the same mint are
+// passed as remaining accounts in the order of the fee distribution
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DistributeTokenFees<'info>>
+) -> Result<()> {
+    let fee_distribution = &ctx.accounts.fee_distribution;
+    require!(
+        ctx.remaining_accounts.len() == fee_distribution.recipients.len(),
+        PlinkoError::InvalidFeeRecipientAccount
+    );
+
+    let available = ctx.accounts.fee_vault_token_account.amount;
+    require!(available > 0, PlinkoError::NothingToDistribute);
+
+    let fee_vault_bump = ctx.bumps.fee_vault;
+    let shares = fee_distribution.shares(available);
+    let mut distributed = 0;
+
+    for ((recipient, recipient_info), share) in fee_distribution.recipients
+        .iter()
+        .zip(ctx.remaining_accounts.iter())
+        .zip(shares) {
+        let recipient_token_account = InterfaceAccount::<TokenAccount>::try_from(recipient_info)?;
+        require!(
+            recipient_token_account.owner == recipient.recipient &&
+                recipient_token_account.mint == ctx.accounts.mint.key(),
+            PlinkoError::InvalidFeeRecipientAccount
+        );
+
+        if share > 0 {
+            token_transfer_with_signer(
+                ctx.accounts.fee_vault_token_account.to_account_info(),
+                recipient_info.clone(),
+                ctx.accounts.fee_vault.to_account_info(),
+                ctx.accounts.mint.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                &[&[b"fee_vault", &[fee_vault_bump]]],
+                share,
+                ctx.accounts.mint.decimals
+            )?;
+        }
+        distributed += share;
+        msg!("Paid {} to {}", share, recipient.recipient);
+    }
+
+    // Token amounts can't be added to the lamport total, each mint keeps its own
+    ctx.accounts.mint_config.total_distributed += distributed;
+
+    msg!("Token fees distributed");
+    msg!("Mint: {}", ctx.accounts.mint.key());
+    msg!("Total: {}", distributed);
+
+    Ok(())
+}
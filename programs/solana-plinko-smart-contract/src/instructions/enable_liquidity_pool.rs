@@ -0,0 +1,106 @@
+use crate::account::*;
+use crate::errors::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{ Mint, TokenAccount, TokenInterface };
+
+#[derive(Accounts)]
+pub struct EnableLiquidityPool<'info> {
+    #[account(seeds = [b"plinko_status"], bump)]
+    pub plinko_status: Box<Account<'info, PlinkoStatus>>,
+
+    #[account(
+        mut,
+        seeds = [b"house"],
+        bump,
+        constraint = !house.lp_enabled @ PlinkoError::LiquidityPoolActive
+    )]
+    pub house: Box<Account<'info, House>>,
+
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account(seeds = [b"vaultseed"], bump)]
+    pub vault: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"lp_mint"],
+        bump,
+        mint::decimals = 9,
+        mint::authority = vault,
+        mint::token_program = token_program
+    )]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: Receives the shares for the existing bankroll, any key the owner picks
+    pub seed_share_owner: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = lp_mint,
+        associated_token::authority = seed_share_owner,
+        associated_token::token_program = token_program
+    )]
+    pub seed_share_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Only needed once the multisig owns the program
+    #[account(seeds = [b"multisig"], bump = multisig.bump)]
+    pub multisig: Option<Box<Account<'info, Multisig>>>,
+
+    // The multisig transaction approving `EnableLiquidityPool`, its proposer runs it
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"multisig_tx", transaction.id.to_le_bytes().as_ref()],
+        bump = transaction.bump,
+        constraint = transaction.proposer == authority.key() @ PlinkoError::OnlyTransactionProposer
+    )]
+    pub transaction: Option<Box<Account<'info, MultisigTransaction>>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<EnableLiquidityPool>) -> Result<()> {
+    let seed_share_owner = ctx.accounts.seed_share_owner.key();
+    check_owner_or_multisig(
+        &ctx.accounts.plinko_status.owner,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.multisig.as_deref(),
+        ctx.accounts.transaction.as_deref(),
+        &(MultisigAction::EnableLiquidityPool { seed_share_owner })
+    )?;
+
+    // The existing bankroll becomes the owner's stake, one share per lamport
+    let seed_shares = ctx.accounts.house.net_asset_value(ctx.accounts.vault.lamports())?;
+
+    if seed_shares > 0 {
+        let vault_bump = ctx.bumps.vault;
+        token_mint_to_with_signer(
+            ctx.accounts.lp_mint.to_account_info(),
+            ctx.accounts.seed_share_account.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            &[&[b"vaultseed", &[vault_bump]]],
+            seed_shares
+        )?;
+    }
+
+    let house = &mut ctx.accounts.house;
+    house.lp_enabled = true;
+    house.balance = ctx.accounts.vault.lamports();
+
+    msg!("Liquidity pool enabled");
+    msg!("LP mint: {}", ctx.accounts.lp_mint.key());
+    msg!("Seed shares: {} to {}", seed_shares, seed_share_owner);
+
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use crate::account::*;
+use crate::errors::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct ExecuteChange<'info> {
+    #[account(mut, seeds = [b"plinko_status"], bump)]
+    pub plinko_status: Account<'info, PlinkoStatus>,
+
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [b"change", id.to_le_bytes().as_ref()],
+        bump = pending_change.bump
+    )]
+    pub pending_change: Box<Account<'info, PendingChange>>,
+
+    // Only needed for payout changes
+    #[account(
+        init_if_needed,
+        payer = executor,
+        space = 8 + PayoutTable::LEN,
+        seeds = [
+            b"payout_table".as_ref(),
+            &[pending_change.change.payout_table_key().0],
+            &[pending_change.change.payout_table_key().1],
+        ],
+        bump
+    )]
+    pub payout_table: Option<Box<Account<'info, PayoutTable>>>,
+
+    // Only needed for fee distribution changes
+    #[account(
+        init_if_needed,
+        payer = executor,
+        space = 8 + FeeDistribution::LEN,
+        seeds = [b"fee_distribution"],
+        bump
+    )]
+    pub fee_distribution: Option<Box<Account<'info, FeeDistribution>>>,
+
+    /// CHECK: Collects platform fees until they are distributed, only needed for fee distribution changes
+    #[account(mut, seeds = [b"fee_vault"], bump)]
+    pub fee_vault: Option<AccountInfo<'info>>,
+
+    // Only needed for fee tier changes
+    #[account(
+        init_if_needed,
+        payer = executor,
+        space = 8 + FeeTiers::LEN,
+        seeds = [b"fee_tiers"],
+        bump
+    )]
+    pub fee_tiers: Option<Box<Account<'info, FeeTiers>>>,
+
+    // Only needed for token setting changes
+    #[account(mut, seeds = [b"mint_config", mint_config.mint.as_ref()], bump = mint_config.bump)]
+    pub mint_config: Option<Box<Account<'info, MintConfig>>>,
+
+    /// CHECK: This account is the proposer of the change, receives the rent back
+    #[account(mut, address = pending_change.proposer)]
+    pub proposer: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub executor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ExecuteChange>, id: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        ctx.accounts.pending_change.is_ready(now, ctx.accounts.plinko_status.change_delay),
+        PlinkoError::ChangeNotReady
+    );
+
+    let change = ctx.accounts.pending_change.change.clone();
+    if let ParameterChange::FeeDistribution(_) = change {
+        // Keep the fee vault rent exempt, otherwise small fees could not be sent to it
+        let fee_vault = ctx.accounts.fee_vault.as_ref().ok_or(PlinkoError::MissingChangeAccount)?;
+        fund_rent_exemption(
+            fee_vault.to_account_info(),
+            ctx.accounts.executor.to_account_info(),
+            ctx.accounts.system_program.to_account_info()
+        )?;
+    }
+
+    change.apply(&mut ctx.accounts.plinko_status, ChangeAccounts {
+        payout_table: ctx.accounts.payout_table
+            .as_deref_mut()
+            .map(|table| &mut **table)
+            .zip(ctx.bumps.payout_table),
+        fee_distribution: ctx.accounts.fee_distribution
+            .as_deref_mut()
+            .map(|fee_distribution| &mut **fee_distribution)
+            .zip(ctx.bumps.fee_distribution),
+        fee_tiers: ctx.accounts.fee_tiers
+            .as_deref_mut()
+            .map(|fee_tiers| &mut **fee_tiers)
+            .zip(ctx.bumps.fee_tiers),
+        mint_config: ctx.accounts.mint_config
+            .as_deref_mut()
+            .map(|mint_config| &mut **mint_config),
+    })?;
+
+    msg!("Parameter change executed");
+    msg!("Change id: {}", id);
+    msg!("Change: {:?}", ctx.accounts.pending_change.change);
+
+    Ok(())
+}
@@ -0,0 +1,274 @@
+use crate::account::*;
+use crate::errors::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct ExecuteMultisigTransaction<'info> {
+    #[account(mut, seeds = [b"plinko_status"], bump)]
+    pub plinko_status: Box<Account<'info, PlinkoStatus>>,
+
+    #[account(mut, seeds = [b"house"], bump)]
+    pub house: Box<Account<'info, House>>,
+
+    #[account(mut, seeds = [b"multisig"], bump = multisig.bump)]
+    pub multisig: Box<Account<'info, Multisig>>,
+
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [b"multisig_tx", id.to_le_bytes().as_ref()],
+        bump = transaction.bump,
+        constraint = transaction.seqno == multisig.seqno @ PlinkoError::StaleMultisigTransaction,
+        constraint = transaction.approval_count() >= (multisig.threshold as usize) @ PlinkoError::NotEnoughApprovals
+    )]
+    pub transaction: Box<Account<'info, MultisigTransaction>>,
+
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account(mut, seeds = [b"vaultseed"], bump)]
+    pub vault: AccountInfo<'info>,
+
+    /// CHECK: Only needed for withdrawals, checked against the action in the handler
+    #[account(mut)]
+    pub recipient: Option<AccountInfo<'info>>,
+
+    // Only needed for payout changes applied right away
+    #[account(
+        init_if_needed,
+        payer = executor,
+        space = 8 + PayoutTable::LEN,
+        seeds = [
+            b"payout_table".as_ref(),
+            &[transaction.action.payout_table_key().0],
+            &[transaction.action.payout_table_key().1],
+        ],
+        bump
+    )]
+    pub payout_table: Option<Box<Account<'info, PayoutTable>>>,
+
+    // Only needed for changes queued while the change delay is on
+    #[account(
+        init,
+        payer = executor,
+        space = 8 + PendingChange::LEN,
+        seeds = [b"change", plinko_status.next_change_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pending_change: Option<Box<Account<'info, PendingChange>>>,
+
+    // Only needed for fee distribution changes
+    #[account(
+        init_if_needed,
+        payer = executor,
+        space = 8 + FeeDistribution::LEN,
+        seeds = [b"fee_distribution"],
+        bump
+    )]
+    pub fee_distribution: Option<Box<Account<'info, FeeDistribution>>>,
+
+    /// CHECK: Collects platform fees until they are distributed, only needed for fee distribution changes
+    #[account(mut, seeds = [b"fee_vault"], bump)]
+    pub fee_vault: Option<AccountInfo<'info>>,
+
+    // Only needed for fee tier changes
+    #[account(
+        init_if_needed,
+        payer = executor,
+        space = 8 + FeeTiers::LEN,
+        seeds = [b"fee_tiers"],
+        bump
+    )]
+    pub fee_tiers: Option<Box<Account<'info, FeeTiers>>>,
+
+    // Only needed for token setting changes
+    #[account(mut, seeds = [b"mint_config", mint_config.mint.as_ref()], bump = mint_config.bump)]
+    pub mint_config: Option<Box<Account<'info, MintConfig>>>,
+
+    // Only needed for role changes
+    #[account(
+        init_if_needed,
+        payer = executor,
+        space = 8 + Roles::LEN,
+        seeds = [b"roles"],
+        bump
+    )]
+    pub roles: Option<Box<Account<'info, Roles>>>,
+
+    /// CHECK: This account is the proposer of the transaction, receives the rent back
+    #[account(mut, address = transaction.proposer)]
+    pub proposer: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub executor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ExecuteMultisigTransaction>, id: u64) -> Result<()> {
+    let multisig_key = ctx.accounts.multisig.key();
+    let action = ctx.accounts.transaction.action.clone();
+
+    // Accepting ownership is the only action the multisig can take before it is the owner
+    if action == MultisigAction::AcceptOwner {
+        require!(
+            ctx.accounts.plinko_status.pending_owner == multisig_key,
+            PlinkoError::OnlyPendingOwner
+        );
+    } else {
+        require!(
+            ctx.accounts.plinko_status.is_owner(&multisig_key),
+            PlinkoError::MultisigNotOwner
+        );
+    }
+
+    match action {
+        MultisigAction::Withdraw { amount, recipient } => {
+            let recipient_info = ctx.accounts.recipient
+                .as_ref()
+                .ok_or(PlinkoError::MissingActionAccount)?;
+            require_keys_eq!(recipient_info.key(), recipient, PlinkoError::MissingActionAccount);
+            ctx.accounts.house.check_withdrawal(amount, ctx.accounts.vault.lamports())?;
+
+            let vault_bump = ctx.bumps.vault;
+            sol_transfer_with_signer(
+                ctx.accounts.vault.to_account_info(),
+                recipient_info.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+                &[&[b"vaultseed", &[vault_bump]]],
+                amount
+            )?;
+
+            ctx.accounts.house.balance = ctx.accounts.vault.lamports();
+
+            msg!("Withdrew {} lamports from Vault to {}", amount, recipient);
+        }
+        MultisigAction::LockOdds => {
+            ctx.accounts.plinko_status.odds_locked = true;
+
+            msg!("Odds locked successfully");
+        }
+        MultisigAction::Change(change) => {
+            if ctx.accounts.plinko_status.is_timelocked() {
+                let plinko_status = &mut ctx.accounts.plinko_status;
+                let pending_change = ctx.accounts.pending_change
+                    .as_mut()
+                    .ok_or(PlinkoError::MissingActionAccount)?;
+                let now = Clock::get()?.unix_timestamp;
+
+                pending_change.version = PendingChange::VERSION;
+                pending_change.id = plinko_status.next_change_id;
+                pending_change.change = change;
+                pending_change.proposer = multisig_key;
+                pending_change.proposed_at = now;
+                pending_change.execute_after = now + plinko_status.change_delay;
+                pending_change.bump = ctx.bumps.pending_change.ok_or(
+                    PlinkoError::MissingActionAccount
+                )?;
+
+                plinko_status.next_change_id += 1;
+
+                msg!("Parameter change queued");
+                msg!("Change id: {}", pending_change.id);
+                msg!("Executable after: {}", pending_change.execute_after);
+            } else {
+                if let ParameterChange::FeeDistribution(_) = change {
+                    // Keep the fee vault rent exempt, otherwise small fees could not be sent to it
+                    let fee_vault = ctx.accounts.fee_vault
+                        .as_ref()
+                        .ok_or(PlinkoError::MissingActionAccount)?;
+                    fund_rent_exemption(
+                        fee_vault.to_account_info(),
+                        ctx.accounts.executor.to_account_info(),
+                        ctx.accounts.system_program.to_account_info()
+                    )?;
+                }
+
+                change.apply(&mut ctx.accounts.plinko_status, ChangeAccounts {
+                    payout_table: ctx.accounts.payout_table
+                        .as_deref_mut()
+                        .map(|table| &mut **table)
+                        .zip(ctx.bumps.payout_table),
+                    fee_distribution: ctx.accounts.fee_distribution
+                        .as_deref_mut()
+                        .map(|fee_distribution| &mut **fee_distribution)
+                        .zip(ctx.bumps.fee_distribution),
+                    fee_tiers: ctx.accounts.fee_tiers
+                        .as_deref_mut()
+                        .map(|fee_tiers| &mut **fee_tiers)
+                        .zip(ctx.bumps.fee_tiers),
+                    mint_config: ctx.accounts.mint_config
+                        .as_deref_mut()
+                        .map(|mint_config| &mut **mint_config),
+                })?;
+
+                msg!("Parameter change applied");
+            }
+        }
+        MultisigAction::AcceptOwner => {
+            let plinko_status = &mut ctx.accounts.plinko_status;
+            plinko_status.owner = multisig_key;
+            plinko_status.pending_owner = Pubkey::default();
+            ctx.accounts.house.owner = multisig_key;
+
+            msg!("Ownership transferred to multisig {}", multisig_key);
+        }
+        MultisigAction::SetSigners { signers, threshold } => {
+            let multisig = &mut ctx.accounts.multisig;
+            multisig.signers = signers;
+            multisig.threshold = threshold;
+            multisig.seqno += 1;
+
+            msg!("Multisig signers updated: {:?}", multisig.signers);
+            msg!("Threshold: {}", threshold);
+        }
+        MultisigAction::ProposeOwner { new_owner } => {
+            let plinko_status = &mut ctx.accounts.plinko_status;
+            require!(
+                new_owner != Pubkey::default() && new_owner != plinko_status.owner,
+                PlinkoError::InvalidNewOwner
+            );
+            plinko_status.pending_owner = new_owner;
+
+            msg!("Ownership transfer proposed");
+            msg!("Pending owner: {}", new_owner);
+        }
+        MultisigAction::CancelOwnerTransfer => {
+            let plinko_status = &mut ctx.accounts.plinko_status;
+            require!(plinko_status.pending_owner != Pubkey::default(), PlinkoError::NoPendingOwner);
+            let cancelled_owner = plinko_status.pending_owner;
+            plinko_status.pending_owner = Pubkey::default();
+
+            msg!("Ownership transfer cancelled");
+            msg!("Cancelled pending owner: {}", cancelled_owner);
+        }
+        MultisigAction::GrantRole { member, role } => {
+            let roles_bump = ctx.bumps.roles.ok_or(PlinkoError::MissingActionAccount)?;
+            let roles = ctx.accounts.roles.as_mut().ok_or(PlinkoError::MissingActionAccount)?;
+            roles.init_if_new(roles_bump);
+            roles.grant(member, role)?;
+
+            msg!("Role granted");
+            msg!("Member: {}", member);
+            msg!("Role: {:?}", role);
+        }
+        MultisigAction::RevokeRole { member, role } => {
+            let roles = ctx.accounts.roles.as_mut().ok_or(PlinkoError::MissingActionAccount)?;
+            roles.revoke(member, role)?;
+
+            msg!("Role revoked");
+            msg!("Member: {}", member);
+            msg!("Role: {:?}", role);
+        }
+        // Approved here, but run by their own instruction which closes the transaction
+        MultisigAction::Migrate { .. } | MultisigAction::EnableLiquidityPool { .. } => {
+            return err!(PlinkoError::ActionHasOwnInstruction);
+        }
+    }
+
+    msg!("Multisig transaction executed");
+    msg!("Transaction id: {}", id);
+    msg!("Executed by: {}", ctx.accounts.executor.key());
+
+    Ok(())
+}
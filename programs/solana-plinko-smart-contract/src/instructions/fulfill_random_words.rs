@@ -1,12 +1,13 @@
 use anchor_lang::prelude::*;
 use crate::account::*;
 use crate::errors::*;
+use crate::events::*;
 use crate::misc::*;
 use crate::utils::*;
 use orao_solana_vrf::RANDOMNESS_ACCOUNT_SEED;
 
 #[derive(Accounts)]
-#[instruction(force: [u8; 32], game_id: u64, request_id: u64)]
+#[instruction(force: [u8; 32], nonce: u64, request_id: u64)]
 pub struct FulFillRandomWords<'info> {
     #[account(
         mut, 
@@ -17,13 +18,21 @@ pub struct FulFillRandomWords<'info> {
 
     #[account(
         mut,
-        seeds = [b"game", game_id.to_le_bytes().as_ref()],
+        seeds = [b"game", player.key().as_ref(), nonce.to_le_bytes().as_ref()],
         bump,
         constraint = game.request_id == request_id  @ PlinkoError::InvalidRequestId,
-        constraint = !game.has_ended @ PlinkoError::GameAlreadyEnded
+        constraint = game.force == force @ PlinkoError::InvalidRandomnessAccount,
+        constraint = game.is_pending() @ PlinkoError::GameAlreadyEnded,
+        constraint = game.is_sol() @ PlinkoError::InvalidMint
     )]
     pub game: Account<'info, Game>,
 
+    #[account(
+        seeds = [b"payout_table".as_ref(), &[game.risk_level as u8], &[game.rows]],
+        bump = payout_table.bump
+    )]
+    pub payout_table: Account<'info, PayoutTable>,
+
     #[account(
         mut,
         seeds = [b"house"],
@@ -31,8 +40,23 @@ pub struct FulFillRandomWords<'info> {
     )]
     pub house: Account<'info, House>,
 
-    #[account (mut, seeds = [b"user_stats", game.player.key().as_ref()], bump)]
-    pub user_stats: Account<'info, UserStats>,
+    #[account(mut, seeds = [b"user_stats", game.player.key().as_ref()], bump)]
+    pub user_stats: Box<Account<'info, UserStats>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"history",
+            game.player.as_ref(),
+            HistoryPage::page_of(game.nonce).to_le_bytes().as_ref(),
+        ],
+        bump = history_page.bump
+    )]
+    pub history_page: Box<Account<'info, HistoryPage>>,
+
+    /// CHECK: Holds the stake until the game is settled
+    #[account(mut, seeds = [b"escrow", game.key().as_ref()], bump)]
+    pub escrow: AccountInfo<'info>,
 
     /// CHECK: This is not dangerous because we don't read or write from this account
     #[account(mut, seeds = [b"vaultseed"], bump)]
@@ -41,7 +65,8 @@ pub struct FulFillRandomWords<'info> {
     /// CHECK: Randomness
     #[account(
         mut,
-        seeds = [RANDOMNESS_ACCOUNT_SEED, &force],
+        address = game.random @ PlinkoError::InvalidRandomnessAccount,
+        seeds = [RANDOMNESS_ACCOUNT_SEED, &game.force],
         bump,
         seeds::program = orao_solana_vrf::ID
     )]
@@ -51,28 +76,37 @@ pub struct FulFillRandomWords<'info> {
     #[account(mut)]
     pub player: AccountInfo<'info>,
 
+    /// CHECK: Only receives lamports, must be the treasury stored in `plinko_status`
+    #[account(mut, address = plinko_status.fee_treasury @ PlinkoError::InvalidFeeTreasury)]
+    pub fee_treasury: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"referral", referral.code.as_ref()],
+        bump = referral.bump,
+        constraint = referral.key() == user_stats.referrer @ PlinkoError::InvalidReferral
+    )]
+    pub referral: Option<Account<'info, Referral>>,
+
+    #[account(mut, seeds = [b"jackpot"], bump = jackpot.bump)]
+    pub jackpot: Option<Box<Account<'info, Jackpot>>>,
+
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(
     ctx: Context<FulFillRandomWords>,
-    force: [u8; 32],
-    game_id: u64,
-    request_id: u64
+    _force: [u8; 32],
+    _nonce: u64,
+    _request_id: u64
 ) -> Result<()> {
     let plinko_status = &mut ctx.accounts.plinko_status;
     let game = &mut ctx.accounts.game;
+    let payout_table = &ctx.accounts.payout_table;
     let user_stats = &mut ctx.accounts.user_stats;
     let house = &mut ctx.accounts.house;
-    let player = &mut ctx.accounts.player;
-    let vault = &mut ctx.accounts.vault;
     let vault_bump = ctx.bumps.vault;
 
-    let mut total_payout = 0u64;
-    let mut buckets = Vec::new();
-    let house_bump = ctx.bumps.house;
-
-    let ball_amount: u8 = game.num_balls;
     let rand_acc = crate::misc::get_account_data(&ctx.accounts.random)?;
 
     let randomness = current_state(&rand_acc);
@@ -81,78 +115,158 @@ pub fn handler(
         return err!(PlinkoError::StillProcessing);
     }
 
-    let randoms: Vec<u16> = plinko_status.derive_many_randoms(randomness, ball_amount.into());
-
-    for (i, randoms) in randoms.iter().enumerate() {
-        let bucket_index = plinko_status.get_bucket_index(*randoms)?;
-        buckets.push(bucket_index);
+    let total_payout = game.drop_balls(plinko_status, payout_table, randomness)?;
+    msg!("Game Round Total Payout: {}", game.payout);
 
-        let ball_payout = plinko_status.get_payout_amount(game.bet_amount_per_ball, bucket_index)?;
-        total_payout = total_payout.checked_add(ball_payout).ok_or(PlinkoError::InsufficientFunds)?;
-    }
+    // Under the winnings and hybrid models the fee on net winnings is kept from the payout.
+    // Like the fee charged up front it gets the player's tier discount and a bound referrer
+    // takes its share
+    let winnings_fee = FeeTier::apply_discount(
+        plinko_status.winnings_fee_amount(total_payout, game.bet_amount)?,
+        game.fee_discount
+    );
+    require!(
+        ctx.accounts.referral.is_some() || !user_stats.has_referrer(),
+        PlinkoError::InvalidReferral
+    );
+    let referral_amount = match ctx.accounts.referral {
+        Some(_) => plinko_status.referral_share(winnings_fee)?,
+        None => 0,
+    };
+    let treasury_amount = winnings_fee - referral_amount;
 
-    game.buckets = buckets;
+    let total_payout = total_payout - winnings_fee;
     game.payout = total_payout;
-    msg!("Game Round Total Payout: {}", game.payout);
+    game.platform_fee += winnings_fee;
+    game.referral_fee += referral_amount;
 
     user_stats.total_won += total_payout;
 
+    plinko_status.total_games += 1;
+    plinko_status.total_volume += game.bet_amount;
     plinko_status.total_payouts += total_payout;
 
-    if total_payout >= game.amount_for_house {
-        require!(house.balance >= total_payout, PlinkoError::InsufficientFunds);
+    let game_key = game.key();
+    let escrow_bump = ctx.bumps.escrow;
+    let escrow_signer: &[&[&[u8]]; 1] = &[&[b"escrow", game_key.as_ref(), &[escrow_bump]]];
+    let escrow_lamports = ctx.accounts.escrow.lamports();
+    let staked = escrow_lamports.min(game.amount_for_house);
 
-        if house.balance < total_payout {
+    if total_payout >= staked {
+        // The stake goes back from escrow, the vault adds the winnings on top
+        let from_vault = total_payout - staked;
+        require!(
+            ctx.accounts.vault.lamports() >= from_vault + winnings_fee,
+            PlinkoError::InsufficientFunds
+        );
+
+        if from_vault > 0 {
             sol_transfer_with_signer(
-                ctx.accounts.vault.to_account_info().clone(),
+                ctx.accounts.vault.to_account_info(),
                 ctx.accounts.player.to_account_info(),
                 ctx.accounts.system_program.to_account_info(),
                 &[&[b"vaultseed", &[vault_bump]]],
-                game.bet_amount
+                from_vault
             )?;
+        }
+        msg!("🎉 Congratulation! Player got the reward!");
+    } else {
+        let remaining = staked - total_payout;
 
-            house.balance -= game.bet_amount - game.amount_for_house;
+        // Track what stays in house
+        sol_transfer_with_signer(
+            ctx.accounts.escrow.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            escrow_signer,
+            remaining
+        )?;
+        msg!("🥺 Sorry, better luck next time.");
+    }
+
+    if treasury_amount > 0 {
+        sol_transfer_with_signer(
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.fee_treasury.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            &[&[b"vaultseed", &[vault_bump]]],
+            treasury_amount
+        )?;
+    }
 
-            msg!("Invalid bet amount!");
-        } else {
+    if let Some(referral) = ctx.accounts.referral.as_mut() {
+        if referral_amount > 0 {
             sol_transfer_with_signer(
-                ctx.accounts.vault.to_account_info().clone(),
-                ctx.accounts.player.to_account_info(),
+                ctx.accounts.vault.to_account_info(),
+                referral.to_account_info(),
                 ctx.accounts.system_program.to_account_info(),
                 &[&[b"vaultseed", &[vault_bump]]],
-                total_payout
+                referral_amount
             )?;
-
-            house.total_payout += game.amount_for_house;
-            house.balance -= total_payout;
-            msg!("🎉 Congratulation! Player got the reward!");
         }
-    } else {
-        let remaining = game.amount_for_house - total_payout;
 
+        referral.total_earned += referral_amount;
+        referral.unclaimed += referral_amount;
+    }
+
+    // Whatever is left in escrow is the player's share plus the rent they paid for it
+    let escrow_remaining = ctx.accounts.escrow.lamports();
+    if escrow_remaining > 0 {
         sol_transfer_with_signer(
-            ctx.accounts.vault.to_account_info().clone(),
+            ctx.accounts.escrow.to_account_info(),
             ctx.accounts.player.to_account_info(),
             ctx.accounts.system_program.to_account_info(),
-            &[&[b"vaultseed", &[vault_bump]]],
-            total_payout
+            escrow_signer,
+            escrow_remaining
         )?;
+    }
 
-        // Track what stays in house
-        house.balance += remaining;
-        msg!("🥺 Sorry, better luck next time.");
+    // Only games that contributed to the pool can win it, the pool is paid in full
+    if game.jackpot_contribution > 0 && plinko_status.is_jackpot_hit(randomness) {
+        let jackpot = ctx.accounts.jackpot.as_mut().ok_or(PlinkoError::MissingJackpot)?;
+        let ended_at = Clock::get()?.unix_timestamp;
+        let amount = jackpot.record_hit(game.player, game_key, ended_at);
+        if amount > 0 {
+            jackpot.sub_lamports(amount)?;
+            ctx.accounts.player.add_lamports(amount)?;
+        }
+
+        game.jackpot_payout = amount;
+        user_stats.total_won += amount;
+
+        emit!(JackpotWon {
+            game: game_key,
+            player: game.player,
+            amount,
+            hit: jackpot.total_hits,
+        });
+        msg!("💰 Jackpot! Player won {} lamports", amount);
     }
 
+    // Rakeback is only earned by bets that settle, refunds and cancellations never credit it
+    user_stats.rakeback_accrued += game.rakeback;
+    house.unclaimed_rakeback += game.rakeback;
+
+    house.total_payout += total_payout;
+    house.balance = ctx.accounts.vault.lamports();
     house.pending_request = house.pending_request.saturating_sub(1);
-    game.has_ended = true;
+    house.release_liability(game.max_payout);
+    game.status = Status::Settled;
     game.ended_at = Clock::get()?.unix_timestamp;
-    plinko_status.status = Status::Finished;
+
+    user_stats.record_game(game);
+    ctx.accounts.history_page.record_game(game)?;
 
     msg!("Game ended successfully");
-    msg!("Game ID: {}", game.game_id);
+    msg!("Game: {}", game.key());
     msg!("Player: {}", game.player);
     msg!("Total payout: {} lamports", total_payout);
+    msg!("Winnings fee: {} lamports", winnings_fee);
+    msg!("Referral fee: {} lamports", referral_amount);
     msg!("Buckets: {:?}", game.buckets);
+    if game.rows > 0 {
+        msg!("Paths: {:?}", game.paths);
+    }
 
     Ok(())
 }
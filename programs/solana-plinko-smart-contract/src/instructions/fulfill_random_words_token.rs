@@ -0,0 +1,255 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{ Mint, TokenAccount, TokenInterface };
+use crate::account::*;
+use crate::errors::*;
+use crate::misc::*;
+use crate::utils::*;
+use orao_solana_vrf::RANDOMNESS_ACCOUNT_SEED;
+
+#[derive(Accounts)]
+#[instruction(force: [u8; 32], nonce: u64, request_id: u64)]
+pub struct FulFillRandomWordsToken<'info> {
+    #[account(
+        seeds = [b"plinko_status"], 
+        bump, 
+    )]
+    pub plinko_status: Box<Account<'info, PlinkoStatus>>,
+
+    #[account(
+        mut,
+        seeds = [b"game", game.player.as_ref(), nonce.to_le_bytes().as_ref()],
+        bump,
+        constraint = game.request_id == request_id  @ PlinkoError::InvalidRequestId,
+        constraint = game.force == force @ PlinkoError::InvalidRandomnessAccount,
+        constraint = game.is_pending() @ PlinkoError::GameAlreadyEnded,
+        constraint = game.mint == mint.key() @ PlinkoError::InvalidMint
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    #[account(
+        seeds = [b"payout_table".as_ref(), &[game.risk_level as u8], &[game.rows]],
+        bump = payout_table.bump
+    )]
+    pub payout_table: Box<Account<'info, PayoutTable>>,
+
+    #[account(
+        mut,
+        seeds = [b"mint_config", mint.key().as_ref()],
+        bump = mint_config.bump
+    )]
+    pub mint_config: Box<Account<'info, MintConfig>>,
+
+    #[account(mut, mint::token_program = token_program)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, seeds = [b"user_stats", game.player.key().as_ref()], bump)]
+    pub user_stats: Box<Account<'info, UserStats>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"history",
+            game.player.as_ref(),
+            HistoryPage::page_of(game.nonce).to_le_bytes().as_ref(),
+        ],
+        bump = history_page.bump
+    )]
+    pub history_page: Box<Account<'info, HistoryPage>>,
+
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account(seeds = [b"vaultseed"], bump)]
+    pub vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"token_vault", mint.key().as_ref()],
+        bump = mint_config.vault_bump
+    )]
+    pub vault_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", game.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vault,
+        token::token_program = token_program
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Randomness
+    #[account(
+        mut,
+        address = game.random @ PlinkoError::InvalidRandomnessAccount,
+        seeds = [RANDOMNESS_ACCOUNT_SEED, &game.force],
+        bump,
+        seeds::program = orao_solana_vrf::ID
+    )]
+    pub random: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = game.player,
+        token::token_program = token_program
+    )]
+    pub player_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program,
+        constraint = fee_treasury_token_account.owner == plinko_status.fee_treasury @ PlinkoError::InvalidFeeTreasury
+    )]
+    pub fee_treasury_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Gets back the rent of the escrow token account
+    #[account(mut, address = game.player @ PlinkoError::InvalidPlayer)]
+    pub player: AccountInfo<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(
+    ctx: Context<FulFillRandomWordsToken>,
+    _force: [u8; 32],
+    _nonce: u64,
+    _request_id: u64
+) -> Result<()> {
+    let rand_acc = crate::misc::get_account_data(&ctx.accounts.random)?;
+
+    let randomness = current_state(&rand_acc);
+    msg!("Orao Random number: {}", randomness);
+    if randomness == 0 {
+        return err!(PlinkoError::StillProcessing);
+    }
+
+    let total_payout = ctx.accounts.game.drop_balls(
+        &ctx.accounts.plinko_status,
+        &ctx.accounts.payout_table,
+        randomness
+    )?;
+    msg!("Game Round Total Payout: {}", total_payout);
+
+    // Under the winnings and hybrid models the fee on net winnings is kept from the payout
+    let winnings_fee = ctx.accounts.plinko_status.winnings_fee_amount(
+        total_payout,
+        ctx.accounts.game.bet_amount
+    )?;
+    let total_payout = total_payout - winnings_fee;
+    ctx.accounts.game.payout = total_payout;
+    ctx.accounts.game.platform_fee += winnings_fee;
+
+    let staked = ctx.accounts.escrow_token_account.amount;
+    let vault_bump = ctx.bumps.vault;
+    let vault_signer: &[&[&[u8]]; 1] = &[&[b"vaultseed", &[vault_bump]]];
+    let decimals = ctx.accounts.mint.decimals;
+
+    if total_payout >= staked {
+        // The stake goes back from escrow, the vault adds the winnings on top
+        let from_vault = total_payout - staked;
+        require!(
+            ctx.accounts.vault_token_account.amount >= from_vault + winnings_fee,
+            PlinkoError::InsufficientFunds
+        );
+
+        if staked > 0 {
+            token_transfer_with_signer(
+                ctx.accounts.escrow_token_account.to_account_info(),
+                ctx.accounts.player_token_account.to_account_info(),
+                ctx.accounts.vault.to_account_info(),
+                ctx.accounts.mint.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                vault_signer,
+                staked,
+                decimals
+            )?;
+        }
+        if from_vault > 0 {
+            token_transfer_with_signer(
+                ctx.accounts.vault_token_account.to_account_info(),
+                ctx.accounts.player_token_account.to_account_info(),
+                ctx.accounts.vault.to_account_info(),
+                ctx.accounts.mint.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                vault_signer,
+                from_vault,
+                decimals
+            )?;
+        }
+        msg!("🎉 Congratulation! Player got the reward!");
+    } else {
+        if total_payout > 0 {
+            token_transfer_with_signer(
+                ctx.accounts.escrow_token_account.to_account_info(),
+                ctx.accounts.player_token_account.to_account_info(),
+                ctx.accounts.vault.to_account_info(),
+                ctx.accounts.mint.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                vault_signer,
+                total_payout,
+                decimals
+            )?;
+        }
+        token_transfer_with_signer(
+            ctx.accounts.escrow_token_account.to_account_info(),
+            ctx.accounts.vault_token_account.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            vault_signer,
+            staked - total_payout,
+            decimals
+        )?;
+        msg!("🥺 Sorry, better luck next time.");
+    }
+
+    if winnings_fee > 0 {
+        token_transfer_with_signer(
+            ctx.accounts.vault_token_account.to_account_info(),
+            ctx.accounts.fee_treasury_token_account.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            vault_signer,
+            winnings_fee,
+            decimals
+        )?;
+    }
+
+    token_close_with_signer(
+        ctx.accounts.escrow_token_account.to_account_info(),
+        ctx.accounts.player.to_account_info(),
+        ctx.accounts.vault.to_account_info(),
+        ctx.accounts.mint.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        vault_signer
+    )?;
+    ctx.accounts.vault_token_account.reload()?;
+
+    let mint_config = &mut ctx.accounts.mint_config;
+    mint_config.total_payouts += total_payout;
+    mint_config.balance = ctx.accounts.vault_token_account.amount;
+    mint_config.pending_request = mint_config.pending_request.saturating_sub(1);
+    mint_config.release_liability(ctx.accounts.game.max_payout);
+
+    let game = &mut ctx.accounts.game;
+    game.status = Status::Settled;
+    game.ended_at = Clock::get()?.unix_timestamp;
+
+    ctx.accounts.user_stats.record_game(game);
+    ctx.accounts.history_page.record_game(game)?;
+
+    msg!("Game ended successfully");
+    msg!("Game: {}", game.key());
+    msg!("Player: {}", game.player);
+    msg!("Mint: {}", game.mint);
+    msg!("Total payout: {}", total_payout);
+    msg!("Winnings fee: {}", winnings_fee);
+    msg!("Buckets: {:?}", game.buckets);
+    if game.rows > 0 {
+        msg!("Paths: {:?}", game.paths);
+    }
+
+    Ok(())
+}
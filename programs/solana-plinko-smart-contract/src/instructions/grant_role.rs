@@ -0,0 +1,39 @@
+use crate::account::*;
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct GrantRole<'info> {
+    #[account(
+        seeds = [b"plinko_status"],
+        bump,
+        constraint = plinko_status.is_owner(&authority.key()) @ PlinkoError::OnlyOwner
+    )]
+    pub plinko_status: Account<'info, PlinkoStatus>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + Roles::LEN,
+        seeds = [b"roles"],
+        bump
+    )]
+    pub roles: Account<'info, Roles>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<GrantRole>, member: Pubkey, role: Role) -> Result<()> {
+    let roles = &mut ctx.accounts.roles;
+    roles.init_if_new(ctx.bumps.roles);
+    roles.grant(member, role)?;
+
+    msg!("Role granted");
+    msg!("Member: {}", member);
+    msg!("Role: {:?}", role);
+
+    Ok(())
+}
@@ -32,12 +32,13 @@ pub fn handler(
     max_balls: u8
 ) -> Result<()> {
     require!(platform_fee <= 300, PlinkoError::PlatformFeeTooHigh); // Max 3%
-    require!(max_balls <= 60, PlinkoError::MaxBallsTooHigh); // Max 60 balls
+    require!(max_balls <= Game::MAX_BALLS, PlinkoError::MaxBallsTooHigh);
     require!(min_buy_in > 0, PlinkoError::InvalidValue);
 
     let plinko_status = &mut ctx.accounts.plinko_status;
     let house = &mut ctx.accounts.house;
 
+    plinko_status.version = PlinkoStatus::VERSION;
     plinko_status.owner = ctx.accounts.authority.key();
     plinko_status.platform_fee = platform_fee;
     plinko_status.fee_denominator = 10_000;
@@ -46,20 +47,35 @@ pub fn handler(
     plinko_status.max_balls = max_balls;
     plinko_status.odds_locked = false;
     plinko_status.paused = false;
-    plinko_status.bucket_weights = Vec::new();
-    plinko_status.payouts = Vec::new();
     plinko_status.total_games = 0;
     plinko_status.total_volume = 0;
     plinko_status.total_payouts = 0;
     plinko_status.fee_treasury = ctx.accounts.fee_treasury.key();
     plinko_status.house_account = house.key();
+    plinko_status.refund_timeout = PlinkoStatus::DEFAULT_REFUND_TIMEOUT;
+    plinko_status.refund_platform_fee = false;
+    plinko_status.game_retention = PlinkoStatus::DEFAULT_GAME_RETENTION;
+    plinko_status.pending_owner = Pubkey::default();
+    plinko_status.change_delay = 0; // Changes apply immediately until a delay is queued
+    plinko_status.next_change_id = 0;
+    plinko_status.referral_fee = 0; // Referrers get nothing until a share is set
+    plinko_status.max_integrator_fee = 0; // Integrator fees are off until a cap is set
+    plinko_status.fee_model = FeeModel::Stake;
+    plinko_status.winnings_fee = 0;
+    plinko_status.jackpot_fee = 0;
+    plinko_status.jackpot_odds = 0;
 
+    house.version = House::VERSION;
     house.owner = ctx.accounts.authority.key();
     house.balance = 0;
-    house.maximum_payout = 100;
+    house.max_liability_bps = 10_000; // Pending liabilities fully backed by the vault
     house.total_payout = 0;
     house.withdrawals_pause = false;
     house.pending_request = 0;
+    house.pending_liability = 0;
+    house.lp_enabled = false;
+    house.unclaimed_rakeback = 0;
+    house.fee_refunds_owed = 0;
 
     msg!("Plinko program initialized successfully");
     msg!("Owner: {}", plinko_status.owner);
@@ -8,10 +8,17 @@ pub struct LockOdds<'info> {
         mut,
         seeds = [b"plinko_status"],
         bump,
-        constraint = plinko_status.is_owner(&authority.key()) @ PlinkoError::OnlyOwner
+        constraint = plinko_status.has_role(
+            &authority.key(),
+            roles.as_deref(),
+            Role::OddsManager
+        ) @ PlinkoError::MissingRole
     )]
     pub plinko_status: Account<'info, PlinkoStatus>,
 
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
+
     pub authority: Signer<'info>,
 }
 
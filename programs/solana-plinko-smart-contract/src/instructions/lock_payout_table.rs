@@ -0,0 +1,43 @@
+use crate::account::*;
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(risk_level: RiskLevel, rows: u8)]
+pub struct LockPayoutTable<'info> {
+    #[account(
+        seeds = [b"plinko_status"],
+        bump,
+        constraint = plinko_status.has_role(
+            &authority.key(),
+            roles.as_deref(),
+            Role::OddsManager
+        ) @ PlinkoError::MissingRole
+    )]
+    pub plinko_status: Account<'info, PlinkoStatus>,
+
+    #[account(
+        mut,
+        seeds = [b"payout_table".as_ref(), &[risk_level as u8], &[rows]],
+        bump = payout_table.bump,
+        constraint = payout_table.is_configured() @ PlinkoError::PayoutTableNotConfigured
+    )]
+    pub payout_table: Account<'info, PayoutTable>,
+
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<LockPayoutTable>, risk_level: RiskLevel, rows: u8) -> Result<()> {
+    let payout_table = &mut ctx.accounts.payout_table;
+
+    // Lock this table only, other tables stay editable until `lock_odds`
+    payout_table.locked = true;
+
+    msg!("Payout table locked successfully");
+    msg!("Risk level: {:?}, rows: {}", risk_level, rows);
+
+    Ok(())
+}
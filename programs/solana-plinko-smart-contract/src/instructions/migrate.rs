@@ -0,0 +1,209 @@
+use crate::account::*;
+use crate::errors::*;
+use crate::legacy::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+#[derive(Accounts)]
+pub struct Migrate<'info> {
+    /// CHECK: Read by hand, it may still use an old layout
+    #[account(seeds = [b"plinko_status"], bump, owner = crate::ID)]
+    pub plinko_status: AccountInfo<'info>,
+
+    /// CHECK: Owner is checked here, the layout is detected from the discriminator and version
+    #[account(mut, owner = crate::ID)]
+    pub target: AccountInfo<'info>,
+
+    // Only needed for a version 1 `plinko_status`, receives its single payout table
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + PayoutTable::LEN,
+        seeds = [b"payout_table".as_ref(), &[RiskLevel::Medium as u8], &[0]],
+        bump
+    )]
+    pub payout_table: Option<Box<Account<'info, PayoutTable>>>,
+
+    // Only needed once the multisig owns the program
+    #[account(seeds = [b"multisig"], bump = multisig.bump)]
+    pub multisig: Option<Box<Account<'info, Multisig>>>,
+
+    // The multisig transaction approving `Migrate` for this target, its proposer runs it
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"multisig_tx", transaction.id.to_le_bytes().as_ref()],
+        bump = transaction.bump,
+        constraint = transaction.proposer == authority.key() @ PlinkoError::OnlyTransactionProposer
+    )]
+    pub transaction: Option<Box<Account<'info, MultisigTransaction>>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<Migrate>) -> Result<()> {
+    check_owner_or_multisig(
+        &load_owner(&ctx.accounts.plinko_status)?,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.multisig.as_deref(),
+        ctx.accounts.transaction.as_deref(),
+        &(MultisigAction::Migrate { target: ctx.accounts.target.key() })
+    )?;
+
+    let target = &ctx.accounts.target;
+    let discriminator = {
+        let data = target.try_borrow_data()?;
+        require!(data.len() > 8, PlinkoError::UnknownAccount);
+        data[..8].to_vec()
+    };
+
+    if discriminator == PlinkoStatus::DISCRIMINATOR {
+        let version = stored_version(target, PlinkoStatusV1::SPACE)?;
+        let plinko_status = match version {
+            1 => {
+                let v1 = read::<PlinkoStatusV1>(target)?;
+                let odds_migrated = migrate_odds(
+                    ctx.accounts.payout_table.as_deref_mut(),
+                    ctx.bumps.payout_table,
+                    &v1
+                )?;
+                let mut plinko_status = PlinkoStatus::from(v1);
+                // Nothing to carry over, leave the odds open so the owner can set them
+                if !odds_migrated {
+                    plinko_status.odds_locked = false;
+                }
+                plinko_status
+            }
+            _ => {
+                return unsupported::<PlinkoStatus>(version);
+            }
+        };
+        write_account(&ctx, version, plinko_status)?;
+    } else if discriminator == House::DISCRIMINATOR {
+        let version = stored_version(target, HouseV1::SPACE)?;
+        let house = match version {
+            1 => House::from(read::<HouseV1>(target)?),
+            _ => {
+                return unsupported::<House>(version);
+            }
+        };
+        write_account(&ctx, version, house)?;
+    } else if discriminator == Game::DISCRIMINATOR {
+        let version = stored_version(target, GameV1::SPACE)?;
+        let game = match version {
+            1 => {
+                let v1 = read::<GameV1>(target)?;
+                // A pending game cannot settle under the new layout, its player refunds it
+                require!(v1.has_ended, PlinkoError::LegacyGamePending);
+                Game::from(v1)
+            }
+            _ => {
+                return unsupported::<Game>(version);
+            }
+        };
+        write_account(&ctx, version, game)?;
+    } else if discriminator == UserStats::DISCRIMINATOR {
+        let version = stored_version(target, UserStatsV1::SPACE)?;
+        let user_stats = match version {
+            1 => UserStats::from(read::<UserStatsV1>(target)?),
+            _ => {
+                return unsupported::<UserStats>(version);
+            }
+        };
+        write_account(&ctx, version, user_stats)?;
+    } else {
+        return err!(PlinkoError::UnknownAccount);
+    }
+
+    msg!("Account migrated: {}", target.key());
+
+    Ok(())
+}
+
+// Version 1 kept one weighted bucket table on `plinko_status`, it becomes the Medium table
+fn migrate_odds(
+    payout_table: Option<&mut Account<PayoutTable>>,
+    bump: Option<u8>,
+    v1: &PlinkoStatusV1
+) -> Result<bool> {
+    if PayoutTable::validate(0, &v1.bucket_weights, &v1.payouts).is_err() {
+        msg!("No valid odds to migrate, set them with set_payout");
+        return Ok(false);
+    }
+
+    let (Some(payout_table), Some(bump)) = (payout_table, bump) else {
+        return err!(PlinkoError::MissingPayoutTable);
+    };
+    payout_table.configure(
+        RiskLevel::Medium,
+        0,
+        v1.bucket_weights.clone(),
+        v1.payouts.clone(),
+        bump
+    );
+
+    msg!("Odds migrated to the Medium payout table: {} buckets", payout_table.payouts.len());
+
+    Ok(true)
+}
+
+// Every layout keeps the owner first, right after the version byte once there is one
+fn load_owner(plinko_status: &AccountInfo) -> Result<Pubkey> {
+    let data = plinko_status.try_borrow_data()?;
+    let offset = if data.len() == PlinkoStatusV1::SPACE { 8 } else { 9 };
+    Ok(Pubkey::deserialize(&mut &data[offset..])?)
+}
+
+// Version 1 has no version byte and is recognised by its size, later layouts store their version
+fn stored_version(target: &AccountInfo, v1_space: usize) -> Result<u8> {
+    let data = target.try_borrow_data()?;
+    if data.len() == v1_space {
+        Ok(1)
+    } else {
+        Ok(data[8])
+    }
+}
+
+fn read<V: AnchorDeserialize>(target: &AccountInfo) -> Result<V> {
+    Ok(V::deserialize(&mut &target.try_borrow_data()?[8..])?)
+}
+
+fn unsupported<T: Versioned>(version: u8) -> Result<()> {
+    if version == T::VERSION {
+        err!(PlinkoError::AccountAlreadyMigrated)
+    } else {
+        err!(PlinkoError::UnsupportedVersion)
+    }
+}
+
+fn write_account<T>(ctx: &Context<Migrate>, version: u8, account: T) -> Result<()>
+    where T: Versioned + AccountSerialize
+{
+    let target = &ctx.accounts.target;
+
+    let rent = Rent::get()?.minimum_balance(T::SPACE);
+    let lamports = target.lamports();
+    if rent > lamports {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: target.to_account_info(),
+                }
+            ),
+            rent - lamports
+        )?;
+    }
+
+    target.resize(T::SPACE)?;
+    account.try_serialize(&mut &mut target.try_borrow_mut_data()?[..])?;
+
+    msg!("Layout version: {} -> {}", version, T::VERSION);
+
+    Ok(())
+}
@@ -1,21 +1,123 @@
+#![allow(ambiguous_glob_reexports)]
+
+pub mod accept_owner;
+pub mod add_mint;
+pub mod approve_multisig_transaction;
+pub mod bind_referrer;
+pub mod cancel_change;
+pub mod cancel_game;
+pub mod cancel_game_token;
+pub mod cancel_multisig_transaction;
+pub mod cancel_owner_transfer;
+pub mod claim_rakeback;
+pub mod claim_referral_earnings;
+pub mod close_game;
+pub mod close_games;
+pub mod close_legacy_game;
+pub mod create_jackpot;
+pub mod create_multisig;
+pub mod deposit_liquidity;
+pub mod distribute_fees;
+pub mod distribute_token_fees;
+pub mod enable_liquidity_pool;
+pub mod execute_change;
+pub mod execute_multisig_transaction;
 pub mod fulfill_random_words;
+pub mod fulfill_random_words_token;
+pub mod grant_role;
 pub mod initialize;
 pub mod lock_odds;
+pub mod lock_payout_table;
+pub mod migrate;
 pub mod play_game;
+pub mod play_game_token;
+pub mod propose_change;
+pub mod propose_multisig_transaction;
+pub mod propose_owner;
+pub mod redeem_liquidity;
+pub mod refund_expired_game;
+pub mod refund_expired_game_token;
+pub mod repay_fee_refunds;
+pub mod register_integrator;
+pub mod register_referrer;
+pub mod revoke_role;
+pub mod set_fee_distribution;
+pub mod set_fee_model;
+pub mod set_fee_tiers;
+pub mod set_fee_treasury;
+pub mod set_game_retention;
+pub mod set_jackpot;
 pub mod set_max_balls;
+pub mod set_max_integrator_fee;
+pub mod set_max_liability;
+pub mod set_mint_config;
 pub mod set_min_buy_in;
 pub mod set_paused;
 pub mod set_payout;
 pub mod set_platform_fee;
+pub mod set_referral_fee;
+pub mod set_refund_timeout;
+pub mod set_withdrawals_pause;
 pub mod withdraw_from_vault;
+pub mod withdraw_token_from_vault;
 
+pub use accept_owner::*;
+pub use add_mint::*;
+pub use approve_multisig_transaction::*;
+pub use bind_referrer::*;
+pub use cancel_change::*;
+pub use cancel_game::*;
+pub use cancel_game_token::*;
+pub use cancel_multisig_transaction::*;
+pub use cancel_owner_transfer::*;
+pub use claim_rakeback::*;
+pub use claim_referral_earnings::*;
+pub use close_game::*;
+pub use close_games::*;
+pub use close_legacy_game::*;
+pub use create_jackpot::*;
+pub use create_multisig::*;
+pub use deposit_liquidity::*;
+pub use distribute_fees::*;
+pub use distribute_token_fees::*;
+pub use enable_liquidity_pool::*;
+pub use execute_change::*;
+pub use execute_multisig_transaction::*;
 pub use fulfill_random_words::*;
+pub use fulfill_random_words_token::*;
+pub use grant_role::*;
 pub use initialize::*;
 pub use lock_odds::*;
+pub use lock_payout_table::*;
+pub use migrate::*;
 pub use play_game::*;
+pub use play_game_token::*;
+pub use propose_change::*;
+pub use propose_multisig_transaction::*;
+pub use propose_owner::*;
+pub use redeem_liquidity::*;
+pub use refund_expired_game::*;
+pub use refund_expired_game_token::*;
+pub use repay_fee_refunds::*;
+pub use register_integrator::*;
+pub use register_referrer::*;
+pub use revoke_role::*;
+pub use set_fee_distribution::*;
+pub use set_fee_model::*;
+pub use set_fee_tiers::*;
+pub use set_fee_treasury::*;
+pub use set_game_retention::*;
+pub use set_jackpot::*;
 pub use set_max_balls::*;
+pub use set_max_integrator_fee::*;
+pub use set_max_liability::*;
+pub use set_mint_config::*;
 pub use set_min_buy_in::*;
 pub use set_paused::*;
 pub use set_payout::*;
 pub use set_platform_fee::*;
+pub use set_referral_fee::*;
+pub use set_refund_timeout::*;
+pub use set_withdrawals_pause::*;
 pub use withdraw_from_vault::*;
+pub use withdraw_token_from_vault::*;
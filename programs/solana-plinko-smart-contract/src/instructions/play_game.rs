@@ -1,5 +1,6 @@
 use crate::account::*;
 use crate::errors::*;
+use crate::events::*;
 use anchor_lang::prelude::*;
 use orao_solana_vrf::program::OraoVrf;
 use orao_solana_vrf::state::NetworkState;
@@ -7,26 +8,57 @@ use orao_solana_vrf::CONFIG_ACCOUNT_SEED;
 use orao_solana_vrf::RANDOMNESS_ACCOUNT_SEED;
 
 #[derive(Accounts)]
-#[instruction(force: [u8; 32], game_id: u64)]
+#[instruction(force: [u8; 32], num_balls: u8, risk_level: RiskLevel, rows: u8)]
 pub struct PlayGame<'info> {
     #[account(
-        mut,
         seeds = [b"plinko_status"],
         bump,
-        constraint = !plinko_status.paused @ PlinkoError::GamePaused,
-        constraint = !plinko_status.bucket_weights.is_empty() @ PlinkoError::InvalidBucketIndex
+        constraint = !plinko_status.paused @ PlinkoError::GamePaused
     )]
     pub plinko_status: Account<'info, PlinkoStatus>,
 
+    #[account(
+        seeds = [b"payout_table".as_ref(), &[risk_level as u8], &[rows]],
+        bump = payout_table.bump,
+        constraint = payout_table.is_configured() @ PlinkoError::PayoutTableNotConfigured
+    )]
+    pub payout_table: Account<'info, PayoutTable>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + UserStats::LEN,
+        seeds = [b"user_stats", player.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Box<Account<'info, UserStats>>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + HistoryPage::LEN,
+        seeds = [
+            b"history",
+            player.key().as_ref(),
+            HistoryPage::page_of(user_stats.next_nonce).to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub history_page: Box<Account<'info, HistoryPage>>,
+
     #[account(
         init,
         payer = player,
         space = 8 + Game::LEN,
-        seeds = [b"game", game_id.to_le_bytes().as_ref()],
+        seeds = [b"game", player.key().as_ref(), user_stats.next_nonce.to_le_bytes().as_ref()],
         bump
     )]
     pub game: Account<'info, Game>,
 
+    /// CHECK: Holds the stake until the game is settled
+    #[account(mut, seeds = [b"escrow", game.key().as_ref()], bump)]
+    pub escrow: AccountInfo<'info>,
+
     #[account(
         mut,
         seeds = [b"house"],
@@ -34,20 +66,11 @@ pub struct PlayGame<'info> {
     )]
     pub house: Account<'info, House>,
 
-    #[account(
-        init_if_needed,
-        payer = player,
-        space = 8 + UserStats::LEN,
-        seeds = [b"user_stats", player.key().as_ref()],
-        bump
-    )]
-    pub user_stats: Account<'info, UserStats>,
-
     #[account(mut)]
     pub player: Signer<'info>,
 
-    /// CHECK: This is not dangerous because we don't read or write from this account
-    #[account(mut, seeds = [b"vaultseed"], bump)]
+    /// CHECK: Only its balance is read, the stake goes to the escrow
+    #[account(seeds = [b"vaultseed"], bump)]
     pub vault: AccountInfo<'info>,
 
     /// CHECK: Treasury
@@ -73,19 +96,46 @@ pub struct PlayGame<'info> {
 
     pub vrf: Program<'info, OraoVrf>,
 
-    /// CHECK: This account is used to verify the VRF program
-    #[account(mut)]
+    /// CHECK: Only receives lamports, must be the treasury stored in `plinko_status`
+    #[account(mut, address = plinko_status.fee_treasury @ PlinkoError::InvalidFeeTreasury)]
     pub fee_treasury: AccountInfo<'info>,
 
+    #[account(
+        mut,
+        seeds = [b"referral", referral.code.as_ref()],
+        bump = referral.bump,
+        constraint = referral.key() == user_stats.referrer @ PlinkoError::InvalidReferral
+    )]
+    pub referral: Option<Account<'info, Referral>>,
+
+    /// CHECK: Frontend wallet receiving the integrator fee, checked against its stats account
+    #[account(mut)]
+    pub integrator: Option<AccountInfo<'info>>,
+
+    #[account(
+        mut,
+        seeds = [b"integrator", integrator_stats.integrator.as_ref()],
+        bump = integrator_stats.bump
+    )]
+    pub integrator_stats: Option<Box<Account<'info, IntegratorStats>>>,
+
+    #[account(seeds = [b"fee_tiers"], bump = fee_tiers.bump)]
+    pub fee_tiers: Option<Box<Account<'info, FeeTiers>>>,
+
+    #[account(mut, seeds = [b"jackpot"], bump = jackpot.bump)]
+    pub jackpot: Option<Box<Account<'info, Jackpot>>>,
+
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(
     ctx: Context<PlayGame>,
     force: [u8; 32],
-    game_id: u64,
     num_balls: u8,
-    user_bet_amount: u64
+    risk_level: RiskLevel,
+    rows: u8,
+    user_bet_amount: u64,
+    integrator_fee: u64
 ) -> Result<()> {
     require!(
         num_balls > 0 && num_balls <= ctx.accounts.plinko_status.max_balls,
@@ -102,22 +152,82 @@ pub fn handler(
         PlinkoError::InvalidBetAmount
     );
 
-    let plinko_status = &mut ctx.accounts.plinko_status;
+    let plinko_status = &ctx.accounts.plinko_status;
     let game = &mut ctx.accounts.game;
     let user_stats = &mut ctx.accounts.user_stats;
+    let nonce = user_stats.next_nonce;
     let house = &mut ctx.accounts.house;
     let player = &mut ctx.accounts.player;
-    let vault = &mut ctx.accounts.vault;
+    let vault = &ctx.accounts.vault;
 
     let total_bet = user_bet_amount * (num_balls as u64);
 
-    let platform_fee_amount =
-        (total_bet * plinko_status.platform_fee) / plinko_status.fee_denominator;
-    let amount_for_house = total_bet - platform_fee_amount;
-    let bet_amount = (total_bet - platform_fee_amount) / (num_balls as u64);
+    // The player's volume tier, reached with what they wagered before this bet
+    let tier = ctx.accounts.fee_tiers
+        .as_ref()
+        .and_then(|fee_tiers| fee_tiers.tier_for(user_stats.total_wagered))
+        .copied();
+
+    let mut platform_fee_amount = plinko_status.stake_fee(total_bet, plinko_status.platform_fee);
+    if let Some(tier) = tier {
+        platform_fee_amount = tier.discounted_fee(platform_fee_amount);
+    }
+    let rakeback_amount = tier.map_or(0, |tier| tier.rakeback_amount(total_bet));
+
+    // The integrator fee comes out of the bet like the platform fee, up to the configured cap
+    let integrator_amount = match (&ctx.accounts.integrator, &ctx.accounts.integrator_stats) {
+        (Some(integrator), Some(integrator_stats)) => {
+            require_keys_eq!(
+                integrator.key(),
+                integrator_stats.integrator,
+                PlinkoError::InvalidIntegrator
+            );
+            require!(
+                integrator_fee <= plinko_status.max_integrator_fee,
+                PlinkoError::IntegratorFeeTooHigh
+            );
+            total_bet.checked_mul(integrator_fee).ok_or(PlinkoError::InvalidValue)? /
+                plinko_status.fee_denominator
+        }
+        (None, None) => {
+            require!(integrator_fee == 0, PlinkoError::InvalidIntegrator);
+            0
+        }
+        _ => {
+            return err!(PlinkoError::InvalidIntegrator);
+        }
+    };
+
+    // While the jackpot is enabled every bet feeds the pool
+    let jackpot_amount = if plinko_status.jackpot_fee > 0 {
+        require!(ctx.accounts.jackpot.is_some(), PlinkoError::MissingJackpot);
+        plinko_status.jackpot_contribution(total_bet)
+    } else {
+        0
+    };
+
+    let amount_for_house = total_bet - platform_fee_amount - integrator_amount - jackpot_amount;
+    let bet_amount = amount_for_house / (num_balls as u64);
 
     require!(bet_amount > 0, PlinkoError::InvalidBetAmount);
 
+    let max_payout = ctx.accounts.payout_table.max_payout_amount(
+        bet_amount,
+        num_balls,
+        plinko_status.payout_denominator
+    )?;
+
+    // A bound referrer must be passed, it takes its share of the platform fee
+    require!(
+        ctx.accounts.referral.is_some() || !user_stats.has_referrer(),
+        PlinkoError::InvalidReferral
+    );
+    let referral_amount = match ctx.accounts.referral {
+        Some(_) => plinko_status.referral_share(platform_fee_amount)?,
+        None => 0,
+    };
+    let treasury_amount = platform_fee_amount - referral_amount;
+
     anchor_lang::system_program::transfer(
         CpiContext::new(
             ctx.accounts.system_program.to_account_info(),
@@ -126,22 +236,77 @@ pub fn handler(
                 to: ctx.accounts.fee_treasury.to_account_info(),
             }
         ),
-        platform_fee_amount
+        treasury_amount
     )?;
 
+    if let Some(referral) = ctx.accounts.referral.as_mut() {
+        if referral_amount > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: player.to_account_info(),
+                        to: referral.to_account_info(),
+                    }
+                ),
+                referral_amount
+            )?;
+        }
+
+        referral.total_volume += total_bet;
+        referral.total_earned += referral_amount;
+        referral.unclaimed += referral_amount;
+    }
+
+    if let Some(integrator) = &ctx.accounts.integrator {
+        if integrator_amount > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: player.to_account_info(),
+                        to: integrator.to_account_info(),
+                    }
+                ),
+                integrator_amount
+            )?;
+        }
+    }
+    if let Some(integrator_stats) = ctx.accounts.integrator_stats.as_mut() {
+        integrator_stats.total_games += 1;
+        integrator_stats.total_volume += total_bet;
+        integrator_stats.total_fees += integrator_amount;
+    }
+
+    if let Some(jackpot) = ctx.accounts.jackpot.as_mut() {
+        if jackpot_amount > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: player.to_account_info(),
+                        to: jackpot.to_account_info(),
+                    }
+                ),
+                jackpot_amount
+            )?;
+            jackpot.contribute(jackpot_amount);
+        }
+    }
+
+    // The escrow also takes its rent exemption, which goes back to the player on settlement
+    let escrow_rent = Rent::get()?.minimum_balance(0);
     anchor_lang::system_program::transfer(
         CpiContext::new(
             ctx.accounts.system_program.to_account_info(),
             anchor_lang::system_program::Transfer {
                 from: player.to_account_info(),
-                to: vault.to_account_info(),
+                to: ctx.accounts.escrow.to_account_info(),
             }
         ),
-        amount_for_house
+        amount_for_house + escrow_rent
     )?;
 
-    msg!("Plinko {} game started", game_id);
-
     // Orao VRF call request
     let cpi_program = ctx.accounts.vrf.to_account_info();
     let cpi_accounts = orao_solana_vrf::cpi::accounts::RequestV2 {
@@ -154,51 +319,84 @@ pub fn handler(
     let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
     orao_solana_vrf::cpi::request_v2(cpi_ctx, force)?;
 
-    plinko_status.force = force;
-    plinko_status.status = Status::Processing;
-
-    game.game_id = game_id;
+    game.version = Game::VERSION;
+    game.nonce = nonce;
     game.player = player.key();
+    game.mint = Pubkey::default();
     game.bet_amount = total_bet;
     game.amount_for_house = amount_for_house;
+    game.platform_fee = platform_fee_amount;
     game.num_balls = num_balls;
+    game.risk_level = risk_level;
+    game.rows = rows;
     game.bet_amount_per_ball = bet_amount;
     game.buckets = vec![0; num_balls as usize];
+    game.paths = Vec::new();
     game.payout = 0;
-    game.has_ended = false;
+    game.max_payout = max_payout;
+    game.status = Status::Processing;
     game.request_id = 0;
+    game.force = force;
+    game.random = ctx.accounts.random.key();
     game.created_at = Clock::get()?.unix_timestamp;
+    game.expires_at = game.created_at + plinko_status.refund_timeout;
     game.ended_at = 0;
+    game.jackpot_contribution = jackpot_amount;
+    game.jackpot_payout = 0;
+    game.referral_fee = referral_amount;
+    game.fee_discount = tier.map_or(0, |tier| tier.fee_discount);
+    game.rakeback = rakeback_amount;
 
     if user_stats.user == Pubkey::default() {
-        user_stats.user = player.key();
-        user_stats.total_games = 0;
-        user_stats.total_wagered = 0;
-        user_stats.total_won = 0;
-        user_stats.game_ids = Vec::new();
+        user_stats.initialize(player.key());
     }
     user_stats.total_games += 1;
     user_stats.total_wagered += total_bet;
-    user_stats.game_ids.push(game_id);
+    user_stats.next_nonce += 1;
+    user_stats.record_game(game);
 
-    plinko_status.total_games += 1;
-    plinko_status.total_volume += total_bet;
+    let history_page = &mut ctx.accounts.history_page;
+    if history_page.player == Pubkey::default() {
+        history_page.version = HistoryPage::VERSION;
+        history_page.player = player.key();
+        history_page.page = HistoryPage::page_of(nonce);
+        history_page.records = Vec::new();
+        history_page.bump = ctx.bumps.history_page;
+    }
+    history_page.record_game(game)?;
 
-    let vault_lamports = vault.to_account_info().lamports();
-    house.balance = vault_lamports;
+    // House is the only global account a bet writes, the exposure limit needs its running total
+    let vault_lamports = vault.lamports();
     house.pending_request += 1;
+    house.reserve_liability(max_payout, vault_lamports)?;
 
-    let request_id = plinko_status.generate_request_id(game_id, player.key());
+    let request_id = plinko_status.generate_request_id(nonce, player.key());
     game.request_id = request_id;
 
+    emit!(GameCreated {
+        game: game.key(),
+        player: player.key(),
+        nonce,
+        mint: game.mint,
+    });
+
     msg!("Game started successfully");
-    msg!("Game ID: {}", game_id);
+    msg!("Game: {}", game.key());
+    msg!("Nonce: {}", nonce);
     msg!("Player: {}", player.key());
     msg!("Number of balls: {}", num_balls);
+    msg!("Risk level: {:?}", risk_level);
+    msg!("Rows: {}", rows);
     msg!("Bet amount per ball: {} lamports", bet_amount);
     msg!("Total bet: {} lamports", total_bet);
     msg!("Platform fee: {} lamports", platform_fee_amount);
+    msg!("Referral fee: {} lamports", referral_amount);
+    msg!("Integrator fee: {} lamports", integrator_amount);
+    msg!("Rakeback: {} lamports", rakeback_amount);
+    msg!("Jackpot contribution: {} lamports", jackpot_amount);
     msg!("Amount for house: {} lamports", amount_for_house);
+    msg!("Escrow: {}", ctx.accounts.escrow.key());
+    msg!("Max payout: {} lamports", max_payout);
 
     Ok(())
 }
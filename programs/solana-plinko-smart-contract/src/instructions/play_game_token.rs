@@ -0,0 +1,298 @@
+use crate::account::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{ Mint, TokenAccount, TokenInterface };
+use orao_solana_vrf::program::OraoVrf;
+use orao_solana_vrf::state::NetworkState;
+use orao_solana_vrf::CONFIG_ACCOUNT_SEED;
+use orao_solana_vrf::RANDOMNESS_ACCOUNT_SEED;
+
+#[derive(Accounts)]
+#[instruction(force: [u8; 32], num_balls: u8, risk_level: RiskLevel, rows: u8)]
+pub struct PlayGameToken<'info> {
+    #[account(
+        seeds = [b"plinko_status"],
+        bump,
+        constraint = !plinko_status.paused @ PlinkoError::GamePaused
+    )]
+    pub plinko_status: Box<Account<'info, PlinkoStatus>>,
+
+    #[account(
+        seeds = [b"payout_table".as_ref(), &[risk_level as u8], &[rows]],
+        bump = payout_table.bump,
+        constraint = payout_table.is_configured() @ PlinkoError::PayoutTableNotConfigured
+    )]
+    pub payout_table: Box<Account<'info, PayoutTable>>,
+
+    #[account(
+        mut,
+        seeds = [b"mint_config", mint.key().as_ref()],
+        bump = mint_config.bump,
+        constraint = mint_config.enabled @ PlinkoError::MintDisabled
+    )]
+    pub mint_config: Box<Account<'info, MintConfig>>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + UserStats::LEN,
+        seeds = [b"user_stats", player.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Box<Account<'info, UserStats>>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + HistoryPage::LEN,
+        seeds = [
+            b"history",
+            player.key().as_ref(),
+            HistoryPage::page_of(user_stats.next_nonce).to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub history_page: Box<Account<'info, HistoryPage>>,
+
+    #[account(
+        init,
+        payer = player,
+        space = 8 + Game::LEN,
+        seeds = [b"game", player.key().as_ref(), user_stats.next_nonce.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = player,
+        token::token_program = token_program
+    )]
+    pub player_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [b"token_vault", mint.key().as_ref()],
+        bump = mint_config.vault_bump
+    )]
+    pub vault_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account(seeds = [b"vaultseed"], bump)]
+    pub vault: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = player,
+        seeds = [b"escrow", game.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vault,
+        token::token_program = token_program
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program,
+        constraint = fee_treasury_token_account.owner == plinko_status.fee_treasury @ PlinkoError::InvalidFeeTreasury
+    )]
+    pub fee_treasury_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Treasury
+    #[account(mut)]
+    pub treasury: AccountInfo<'info>,
+
+    /// CHECK: Randomness
+    #[account(
+        mut,
+        seeds = [RANDOMNESS_ACCOUNT_SEED, &force],
+        bump,
+        seeds::program = orao_solana_vrf::ID
+    )]
+    pub random: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_ACCOUNT_SEED],
+        bump,
+        seeds::program = orao_solana_vrf::ID
+    )]
+    pub config: Box<Account<'info, NetworkState>>,
+
+    pub vrf: Program<'info, OraoVrf>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<PlayGameToken>,
+    force: [u8; 32],
+    num_balls: u8,
+    risk_level: RiskLevel,
+    rows: u8,
+    user_bet_amount: u64
+) -> Result<()> {
+    require!(
+        num_balls > 0 && num_balls <= ctx.accounts.plinko_status.max_balls,
+        PlinkoError::InvalidNumberOfBalls
+    );
+
+    let total_bet = user_bet_amount
+        .checked_mul(num_balls as u64)
+        .ok_or(PlinkoError::InvalidBetAmount)?;
+
+    require!(
+        user_bet_amount > 0 &&
+            user_bet_amount >= ctx.accounts.mint_config.min_buy_in &&
+            total_bet <= ctx.accounts.player_token_account.amount,
+        PlinkoError::InvalidBetAmount
+    );
+
+    let platform_fee_amount = ctx.accounts.plinko_status.stake_fee(
+        total_bet,
+        ctx.accounts.mint_config.platform_fee
+    );
+    let decimals = ctx.accounts.mint.decimals;
+
+    if platform_fee_amount > 0 {
+        token_transfer(
+            ctx.accounts.player_token_account.to_account_info(),
+            ctx.accounts.fee_treasury_token_account.to_account_info(),
+            ctx.accounts.player.to_account_info(),
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            platform_fee_amount,
+            decimals
+        )?;
+    }
+
+    // Token-2022 transfer fees are withheld from the destination, so only what
+    // actually reached the escrow backs the game
+    token_transfer(
+        ctx.accounts.player_token_account.to_account_info(),
+        ctx.accounts.escrow_token_account.to_account_info(),
+        ctx.accounts.player.to_account_info(),
+        ctx.accounts.mint.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        total_bet - platform_fee_amount,
+        decimals
+    )?;
+    ctx.accounts.escrow_token_account.reload()?;
+
+    let amount_for_house = ctx.accounts.escrow_token_account.amount;
+    let bet_amount = amount_for_house / (num_balls as u64);
+
+    require!(bet_amount > 0, PlinkoError::InvalidBetAmount);
+
+    let max_payout = ctx.accounts.payout_table.max_payout_amount(
+        bet_amount,
+        num_balls,
+        ctx.accounts.plinko_status.payout_denominator
+    )?;
+
+    // Orao VRF call request
+    let cpi_program = ctx.accounts.vrf.to_account_info();
+    let cpi_accounts = orao_solana_vrf::cpi::accounts::RequestV2 {
+        payer: ctx.accounts.player.to_account_info(),
+        network_state: ctx.accounts.config.to_account_info(),
+        treasury: ctx.accounts.treasury.to_account_info(),
+        request: ctx.accounts.random.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    orao_solana_vrf::cpi::request_v2(cpi_ctx, force)?;
+
+    let plinko_status = &ctx.accounts.plinko_status;
+    let mint_config = &mut ctx.accounts.mint_config;
+    let game = &mut ctx.accounts.game;
+    let user_stats = &mut ctx.accounts.user_stats;
+    let nonce = user_stats.next_nonce;
+    let player = &ctx.accounts.player;
+
+    game.version = Game::VERSION;
+    game.nonce = nonce;
+    game.player = player.key();
+    game.mint = mint_config.mint;
+    game.bet_amount = total_bet;
+    game.amount_for_house = amount_for_house;
+    game.platform_fee = platform_fee_amount;
+    game.num_balls = num_balls;
+    game.risk_level = risk_level;
+    game.rows = rows;
+    game.bet_amount_per_ball = bet_amount;
+    game.buckets = vec![0; num_balls as usize];
+    game.paths = Vec::new();
+    game.payout = 0;
+    game.max_payout = max_payout;
+    game.status = Status::Processing;
+    game.request_id = plinko_status.generate_request_id(nonce, player.key());
+    game.force = force;
+    game.random = ctx.accounts.random.key();
+    game.created_at = Clock::get()?.unix_timestamp;
+    game.expires_at = game.created_at + plinko_status.refund_timeout;
+    game.ended_at = 0;
+    game.jackpot_contribution = 0;
+    game.jackpot_payout = 0;
+    game.referral_fee = 0;
+    game.fee_discount = 0;
+    game.rakeback = 0;
+
+    if user_stats.user == Pubkey::default() {
+        user_stats.initialize(player.key());
+    }
+    // Wagered and won totals are tracked in lamports, token volume lives on the mint config
+    user_stats.total_games += 1;
+    user_stats.next_nonce += 1;
+    user_stats.record_game(game);
+
+    let history_page = &mut ctx.accounts.history_page;
+    if history_page.player == Pubkey::default() {
+        history_page.version = HistoryPage::VERSION;
+        history_page.player = player.key();
+        history_page.page = HistoryPage::page_of(nonce);
+        history_page.records = Vec::new();
+        history_page.bump = ctx.bumps.history_page;
+    }
+    history_page.record_game(game)?;
+
+    mint_config.total_games += 1;
+    mint_config.total_volume += total_bet;
+    mint_config.pending_request += 1;
+    mint_config.reserve_liability(max_payout, ctx.accounts.vault_token_account.amount)?;
+
+    emit!(GameCreated {
+        game: game.key(),
+        player: player.key(),
+        nonce,
+        mint: game.mint,
+    });
+
+    msg!("Game started successfully");
+    msg!("Game: {}", game.key());
+    msg!("Nonce: {}", nonce);
+    msg!("Player: {}", player.key());
+    msg!("Mint: {}", mint_config.mint);
+    msg!("Number of balls: {}", num_balls);
+    msg!("Risk level: {:?}", risk_level);
+    msg!("Rows: {}", rows);
+    msg!("Bet amount per ball: {}", bet_amount);
+    msg!("Total bet: {}", total_bet);
+    msg!("Platform fee: {}", platform_fee_amount);
+    msg!("Amount for house: {}", amount_for_house);
+    msg!("Escrow: {}", ctx.accounts.escrow_token_account.key());
+    msg!("Max payout: {}", max_payout);
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use crate::account::*;
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ProposeChange<'info> {
+    #[account(mut, seeds = [b"plinko_status"], bump)]
+    pub plinko_status: Account<'info, PlinkoStatus>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PendingChange::LEN,
+        seeds = [b"change", plinko_status.next_change_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pending_change: Box<Account<'info, PendingChange>>,
+
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ProposeChange>, change: ParameterChange) -> Result<()> {
+    let plinko_status = &mut ctx.accounts.plinko_status;
+    let authority = ctx.accounts.authority.key();
+
+    let authorized = match change.required_role() {
+        Some(role) => plinko_status.has_role(&authority, ctx.accounts.roles.as_deref(), role),
+        None => plinko_status.is_owner(&authority),
+    };
+    require!(authorized, PlinkoError::MissingRole);
+
+    change.validate()?;
+    if let ParameterChange::Payout { .. } = change {
+        require!(!plinko_status.odds_locked, PlinkoError::OddsLocked);
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    let id = plinko_status.next_change_id;
+
+    let pending_change = &mut ctx.accounts.pending_change;
+    pending_change.version = PendingChange::VERSION;
+    pending_change.id = id;
+    pending_change.change = change;
+    pending_change.proposer = authority;
+    pending_change.proposed_at = now;
+    pending_change.execute_after = now + plinko_status.change_delay;
+    pending_change.bump = ctx.bumps.pending_change;
+
+    plinko_status.next_change_id += 1;
+
+    msg!("Parameter change queued");
+    msg!("Change id: {}", id);
+    msg!("Change: {:?}", pending_change.change);
+    msg!("Executable after: {}", pending_change.execute_after);
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use crate::account::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ProposeMultisigTransaction<'info> {
+    #[account(mut, seeds = [b"multisig"], bump = multisig.bump)]
+    pub multisig: Box<Account<'info, Multisig>>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + MultisigTransaction::LEN,
+        seeds = [b"multisig_tx", multisig.next_transaction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub transaction: Box<Account<'info, MultisigTransaction>>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ProposeMultisigTransaction>, action: MultisigAction) -> Result<()> {
+    let multisig = &mut ctx.accounts.multisig;
+    let signer_index = multisig.signer_index(&ctx.accounts.proposer.key())?;
+
+    match &action {
+        MultisigAction::Change(change) => change.validate()?,
+        MultisigAction::SetSigners { signers, threshold } => {
+            Multisig::validate(signers, *threshold)?
+        }
+        _ => {}
+    }
+
+    let id = multisig.next_transaction_id;
+
+    let transaction = &mut ctx.accounts.transaction;
+    transaction.version = MultisigTransaction::VERSION;
+    transaction.id = id;
+    transaction.action = action;
+    transaction.approvals = vec![false; multisig.signers.len()];
+    transaction.approvals[signer_index] = true; // Proposing counts as approving
+    transaction.seqno = multisig.seqno;
+    transaction.proposer = ctx.accounts.proposer.key();
+    transaction.created_at = Clock::get()?.unix_timestamp;
+    transaction.bump = ctx.bumps.transaction;
+
+    multisig.next_transaction_id += 1;
+
+    msg!("Multisig transaction proposed");
+    msg!("Transaction id: {}", id);
+    msg!("Action: {:?}", transaction.action);
+    msg!("Approvals: {}/{}", transaction.approval_count(), multisig.threshold);
+
+    Ok(())
+}
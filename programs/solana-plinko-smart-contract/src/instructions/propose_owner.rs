@@ -0,0 +1,33 @@
+use crate::account::*;
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ProposeOwner<'info> {
+    #[account(
+        mut,
+        seeds = [b"plinko_status"],
+        bump,
+        constraint = plinko_status.is_owner(&authority.key()) @ PlinkoError::OnlyOwner
+    )]
+    pub plinko_status: Account<'info, PlinkoStatus>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ProposeOwner>, new_owner: Pubkey) -> Result<()> {
+    let plinko_status = &mut ctx.accounts.plinko_status;
+    require!(
+        new_owner != Pubkey::default() && new_owner != plinko_status.owner,
+        PlinkoError::InvalidNewOwner
+    );
+
+    // The new owner has to accept, so a mistyped key can't lock the program
+    plinko_status.pending_owner = new_owner;
+
+    msg!("Ownership transfer proposed");
+    msg!("Current owner: {}", plinko_status.owner);
+    msg!("Pending owner: {}", new_owner);
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use crate::account::*;
+use crate::errors::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{ Mint, TokenAccount, TokenInterface };
+
+#[derive(Accounts)]
+pub struct RedeemLiquidity<'info> {
+    #[account(
+        mut,
+        seeds = [b"house"],
+        bump,
+        constraint = house.lp_enabled @ PlinkoError::LiquidityPoolNotEnabled,
+        constraint = house.pending_liability == 0 @ PlinkoError::GamesPending
+    )]
+    pub house: Box<Account<'info, House>>,
+
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account(mut, seeds = [b"vaultseed"], bump)]
+    pub vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"lp_mint"],
+        bump,
+        mint::token_program = token_program
+    )]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = redeemer,
+        token::token_program = token_program
+    )]
+    pub redeemer_share_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub redeemer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RedeemLiquidity>, shares: u64) -> Result<()> {
+    require!(shares > 0, PlinkoError::InvalidValue);
+
+    let house = &mut ctx.accounts.house;
+    let vault_lamports = ctx.accounts.vault.lamports();
+
+    // Same price as deposits. No game is pending, so the value is not waiting on an outcome
+    let net_asset_value = house.net_asset_value(vault_lamports)?;
+    let amount = House::lamports_for_shares(shares, ctx.accounts.lp_mint.supply, net_asset_value)?;
+    require!(amount > 0, PlinkoError::InvalidValue);
+    house.check_redemption(amount, vault_lamports)?;
+
+    token_burn(
+        ctx.accounts.lp_mint.to_account_info(),
+        ctx.accounts.redeemer_share_account.to_account_info(),
+        ctx.accounts.redeemer.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        shares
+    )?;
+
+    let vault_bump = ctx.bumps.vault;
+    sol_transfer_with_signer(
+        ctx.accounts.vault.to_account_info(),
+        ctx.accounts.redeemer.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+        &[&[b"vaultseed", &[vault_bump]]],
+        amount
+    )?;
+
+    house.balance = ctx.accounts.vault.lamports();
+
+    msg!("Liquidity redeemed");
+    msg!("Redeemer: {}", ctx.accounts.redeemer.key());
+    msg!("Shares burned: {}", shares);
+    msg!("Amount: {} lamports", amount);
+
+    Ok(())
+}
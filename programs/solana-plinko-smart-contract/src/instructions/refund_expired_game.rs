@@ -0,0 +1,165 @@
+use crate::account::*;
+use crate::errors::*;
+use crate::misc::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+use orao_solana_vrf::RANDOMNESS_ACCOUNT_SEED;
+
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct RefundExpiredGame<'info> {
+    #[account(seeds = [b"plinko_status"], bump)]
+    pub plinko_status: Account<'info, PlinkoStatus>,
+
+    #[account(
+        mut,
+        seeds = [b"game", player.key().as_ref(), nonce.to_le_bytes().as_ref()],
+        bump,
+        has_one = player @ PlinkoError::InvalidPlayer,
+        constraint = game.is_pending() @ PlinkoError::GameAlreadyEnded,
+        constraint = game.is_sol() @ PlinkoError::InvalidMint
+    )]
+    pub game: Account<'info, Game>,
+
+    #[account(mut, seeds = [b"user_stats", game.player.key().as_ref()], bump)]
+    pub user_stats: Box<Account<'info, UserStats>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"history",
+            game.player.as_ref(),
+            HistoryPage::page_of(game.nonce).to_le_bytes().as_ref(),
+        ],
+        bump = history_page.bump
+    )]
+    pub history_page: Box<Account<'info, HistoryPage>>,
+
+    /// CHECK: Holds the stake until the game is settled
+    #[account(mut, seeds = [b"escrow", game.key().as_ref()], bump)]
+    pub escrow: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"house"],
+        bump
+    )]
+    pub house: Account<'info, House>,
+
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account(mut, seeds = [b"vaultseed"], bump)]
+    pub vault: AccountInfo<'info>,
+
+    /// CHECK: Randomness
+    #[account(
+        address = game.random @ PlinkoError::InvalidRandomnessAccount,
+        seeds = [RANDOMNESS_ACCOUNT_SEED, &game.force],
+        bump,
+        seeds::program = orao_solana_vrf::ID
+    )]
+    pub random: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"referral", referral.code.as_ref()],
+        bump = referral.bump,
+        constraint = referral.key() == user_stats.referrer @ PlinkoError::InvalidReferral
+    )]
+    pub referral: Option<Account<'info, Referral>>,
+
+    #[account(mut, seeds = [b"jackpot"], bump = jackpot.bump)]
+    pub jackpot: Option<Box<Account<'info, Jackpot>>>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RefundExpiredGame>, _nonce: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(ctx.accounts.game.is_expired(now), PlinkoError::GameNotExpired);
+
+    // Once the randomness is public the outcome is known, so only settlement is allowed
+    let rand_acc = get_account_data(&ctx.accounts.random)?;
+    require!(current_state(&rand_acc) == 0, PlinkoError::RandomnessFulfilled);
+
+    // The stake comes back from escrow. The integrator fee went to an outside wallet and is
+    // never refunded, so only the platform fee comes back, split between vault and referral
+    let (fee_from_vault, fee_from_referral) = if ctx.accounts.plinko_status.refund_platform_fee {
+        ctx.accounts.game.platform_fee_refund(ctx.accounts.referral.as_deref())?
+    } else {
+        (0, 0)
+    };
+    let refund = ctx.accounts.game.amount_for_house + fee_from_vault + fee_from_referral;
+
+    let escrow_lamports = ctx.accounts.escrow.lamports();
+    let from_vault =
+        refund - fee_from_referral - escrow_lamports.min(ctx.accounts.game.amount_for_house);
+    require!(ctx.accounts.vault.lamports() >= from_vault, PlinkoError::InsufficientFunds);
+
+    if escrow_lamports > 0 {
+        let game_key = ctx.accounts.game.key();
+        let escrow_bump = ctx.bumps.escrow;
+        sol_transfer_with_signer(
+            ctx.accounts.escrow.to_account_info(),
+            ctx.accounts.player.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            &[&[b"escrow", game_key.as_ref(), &[escrow_bump]]],
+            escrow_lamports
+        )?;
+    }
+
+    if from_vault > 0 {
+        let vault_bump = ctx.bumps.vault;
+        sol_transfer_with_signer(
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.player.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            &[&[b"vaultseed", &[vault_bump]]],
+            from_vault
+        )?;
+    }
+
+    if fee_from_referral > 0 {
+        if let Some(referral) = ctx.accounts.referral.as_mut() {
+            referral.sub_lamports(fee_from_referral)?;
+            referral.total_earned -= fee_from_referral;
+            referral.unclaimed -= fee_from_referral;
+        }
+        ctx.accounts.player.add_lamports(fee_from_referral)?;
+    }
+
+    // The jackpot contribution comes back from the pool, unless a hit has paid it out since
+    let mut from_jackpot = 0;
+    if ctx.accounts.game.jackpot_contribution > 0 {
+        let jackpot = ctx.accounts.jackpot.as_mut().ok_or(PlinkoError::MissingJackpot)?;
+        from_jackpot = jackpot.withdraw_contribution(ctx.accounts.game.jackpot_contribution);
+        if from_jackpot > 0 {
+            jackpot.sub_lamports(from_jackpot)?;
+            ctx.accounts.player.add_lamports(from_jackpot)?;
+        }
+    }
+
+    let house = &mut ctx.accounts.house;
+    house.balance = ctx.accounts.vault.lamports();
+    house.pending_request = house.pending_request.saturating_sub(1);
+    house.release_liability(ctx.accounts.game.max_payout);
+    house.advance_fee_refund(fee_from_vault);
+
+    let game = &mut ctx.accounts.game;
+    game.status = Status::Refunded;
+    game.ended_at = now;
+
+    ctx.accounts.user_stats.remove_wager(game);
+    ctx.accounts.user_stats.record_game(game);
+    ctx.accounts.history_page.record_game(game)?;
+
+    msg!("Game refunded");
+    msg!("Game: {}", game.key());
+    msg!("Player: {}", game.player);
+    msg!("Refund: {} lamports", refund);
+    msg!("Jackpot contribution refunded: {} lamports", from_jackpot);
+
+    Ok(())
+}
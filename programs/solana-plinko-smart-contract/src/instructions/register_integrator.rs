@@ -0,0 +1,35 @@
+use crate::account::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RegisterIntegrator<'info> {
+    #[account(
+        init,
+        payer = integrator,
+        space = 8 + IntegratorStats::LEN,
+        seeds = [b"integrator", integrator.key().as_ref()],
+        bump
+    )]
+    pub integrator_stats: Account<'info, IntegratorStats>,
+
+    #[account(mut)]
+    pub integrator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RegisterIntegrator>) -> Result<()> {
+    let integrator_stats = &mut ctx.accounts.integrator_stats;
+    integrator_stats.version = IntegratorStats::VERSION;
+    integrator_stats.integrator = ctx.accounts.integrator.key();
+    integrator_stats.total_games = 0;
+    integrator_stats.total_volume = 0;
+    integrator_stats.total_fees = 0;
+    integrator_stats.bump = ctx.bumps.integrator_stats;
+
+    msg!("Integrator registered");
+    msg!("Integrator: {}", integrator_stats.integrator);
+    msg!("Integrator stats: {}", integrator_stats.key());
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use crate::account::*;
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(code: [u8; Referral::CODE_LEN])]
+pub struct RegisterReferrer<'info> {
+    #[account(
+        init,
+        payer = referrer,
+        space = 8 + Referral::LEN,
+        seeds = [b"referral", code.as_ref()],
+        bump
+    )]
+    pub referral: Account<'info, Referral>,
+
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RegisterReferrer>, code: [u8; Referral::CODE_LEN]) -> Result<()> {
+    require!(code != [0; Referral::CODE_LEN], PlinkoError::InvalidReferralCode);
+
+    let referral = &mut ctx.accounts.referral;
+    referral.version = Referral::VERSION;
+    referral.code = code;
+    referral.referrer = ctx.accounts.referrer.key();
+    referral.total_referred = 0;
+    referral.total_volume = 0;
+    referral.total_earned = 0;
+    referral.unclaimed = 0;
+    referral.bump = ctx.bumps.referral;
+
+    msg!("Referrer registered");
+    msg!("Referral: {}", referral.key());
+    msg!("Referrer: {}", referral.referrer);
+
+    Ok(())
+}
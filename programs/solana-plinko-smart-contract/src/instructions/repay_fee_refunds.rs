@@ -0,0 +1,49 @@
+use crate::account::*;
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RepayFeeRefunds<'info> {
+    #[account(
+        mut,
+        seeds = [b"house"],
+        bump
+    )]
+    pub house: Account<'info, House>,
+
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account(mut, seeds = [b"vaultseed"], bump)]
+    pub vault: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Pays back every platform fee the vault refunded for the fee side, anyone can settle it
+pub fn handler(ctx: Context<RepayFeeRefunds>) -> Result<()> {
+    let amount = ctx.accounts.house.fee_refunds_owed;
+    require!(amount > 0, PlinkoError::NothingToRepay);
+
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            }
+        ),
+        amount
+    )?;
+
+    let house = &mut ctx.accounts.house;
+    house.fee_refunds_owed = 0;
+    house.balance = ctx.accounts.vault.lamports();
+
+    msg!("Fee refunds repaid");
+    msg!("Payer: {}", ctx.accounts.payer.key());
+    msg!("Amount: {} lamports", amount);
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use crate::account::*;
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RevokeRole<'info> {
+    #[account(
+        seeds = [b"plinko_status"],
+        bump,
+        constraint = plinko_status.is_owner(&authority.key()) @ PlinkoError::OnlyOwner
+    )]
+    pub plinko_status: Account<'info, PlinkoStatus>,
+
+    #[account(mut, seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Account<'info, Roles>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RevokeRole>, member: Pubkey, role: Role) -> Result<()> {
+    ctx.accounts.roles.revoke(member, role)?;
+
+    msg!("Role revoked");
+    msg!("Member: {}", member);
+    msg!("Role: {:?}", role);
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use crate::account::*;
+use crate::errors::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetFeeDistribution<'info> {
+    #[account(
+        mut,
+        seeds = [b"plinko_status"],
+        bump,
+        constraint = plinko_status.has_role(
+            &authority.key(),
+            roles.as_deref(),
+            Role::Treasurer
+        ) @ PlinkoError::MissingRole,
+        constraint = !plinko_status.is_timelocked() @ PlinkoError::ChangeTimelocked
+    )]
+    pub plinko_status: Account<'info, PlinkoStatus>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + FeeDistribution::LEN,
+        seeds = [b"fee_distribution"],
+        bump
+    )]
+    pub fee_distribution: Account<'info, FeeDistribution>,
+
+    /// CHECK: Collects platform fees until they are distributed
+    #[account(mut, seeds = [b"fee_vault"], bump)]
+    pub fee_vault: AccountInfo<'info>,
+
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SetFeeDistribution>, recipients: Vec<FeeRecipient>) -> Result<()> {
+    FeeDistribution::validate(&recipients)?;
+
+    let fee_distribution = &mut ctx.accounts.fee_distribution;
+    fee_distribution.configure(recipients, ctx.bumps.fee_distribution);
+
+    // Keep the fee vault rent exempt, otherwise small fees could not be sent to it
+    fund_rent_exemption(
+        ctx.accounts.fee_vault.to_account_info(),
+        ctx.accounts.authority.to_account_info(),
+        ctx.accounts.system_program.to_account_info()
+    )?;
+
+    // Platform fees collect in the fee vault from now on
+    ctx.accounts.plinko_status.fee_treasury = ctx.accounts.fee_vault.key();
+
+    msg!("Fee distribution updated");
+    for recipient in fee_distribution.recipients.iter() {
+        msg!("Recipient: {} ({} bps)", recipient.recipient, recipient.bps);
+    }
+    msg!("Fee vault: {}", ctx.accounts.fee_vault.key());
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use crate::account::*;
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetFeeModel<'info> {
+    #[account(
+        mut,
+        seeds = [b"plinko_status"],
+        bump,
+        constraint = plinko_status.has_role(
+            &authority.key(),
+            roles.as_deref(),
+            Role::Operator
+        ) @ PlinkoError::MissingRole,
+        constraint = !plinko_status.is_timelocked() @ PlinkoError::ChangeTimelocked
+    )]
+    pub plinko_status: Account<'info, PlinkoStatus>,
+
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetFeeModel>, fee_model: FeeModel, winnings_fee: u64) -> Result<()> {
+    (ParameterChange::FeeModel { fee_model, winnings_fee }).validate()?;
+
+    let plinko_status = &mut ctx.accounts.plinko_status;
+    plinko_status.fee_model = fee_model;
+    plinko_status.winnings_fee = winnings_fee;
+
+    msg!("Fee model updated to {:?}", fee_model);
+    msg!("Winnings fee: {} basis points", winnings_fee);
+
+    Ok(())
+}
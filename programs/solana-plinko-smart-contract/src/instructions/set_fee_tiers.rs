@@ -0,0 +1,54 @@
+use crate::account::*;
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetFeeTiers<'info> {
+    #[account(
+        seeds = [b"plinko_status"],
+        bump,
+        constraint = plinko_status.has_role(
+            &authority.key(),
+            roles.as_deref(),
+            Role::Operator
+        ) @ PlinkoError::MissingRole,
+        constraint = !plinko_status.is_timelocked() @ PlinkoError::ChangeTimelocked
+    )]
+    pub plinko_status: Account<'info, PlinkoStatus>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + FeeTiers::LEN,
+        seeds = [b"fee_tiers"],
+        bump
+    )]
+    pub fee_tiers: Box<Account<'info, FeeTiers>>,
+
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SetFeeTiers>, tiers: Vec<FeeTier>) -> Result<()> {
+    FeeTiers::validate(&tiers)?;
+
+    let fee_tiers = &mut ctx.accounts.fee_tiers;
+    fee_tiers.configure(tiers, ctx.bumps.fee_tiers);
+
+    msg!("Fee tiers updated");
+    for tier in fee_tiers.tiers.iter() {
+        msg!(
+            "From {} lamports wagered: {} bps fee discount, {} bps rakeback",
+            tier.min_wagered,
+            tier.fee_discount,
+            tier.rakeback
+        );
+    }
+
+    Ok(())
+}
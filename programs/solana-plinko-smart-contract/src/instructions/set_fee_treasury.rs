@@ -0,0 +1,38 @@
+use crate::account::*;
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetFeeTreasury<'info> {
+    #[account(
+        mut,
+        seeds = [b"plinko_status"],
+        bump,
+        constraint = plinko_status.has_role(
+            &authority.key(),
+            roles.as_deref(),
+            Role::Treasurer
+        ) @ PlinkoError::MissingRole,
+        constraint = !plinko_status.is_timelocked() @ PlinkoError::ChangeTimelocked
+    )]
+    pub plinko_status: Account<'info, PlinkoStatus>,
+
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetFeeTreasury>, fee_treasury: Pubkey) -> Result<()> {
+    ParameterChange::FeeTreasury(fee_treasury).validate()?;
+
+    let plinko_status = &mut ctx.accounts.plinko_status;
+    let old_fee_treasury = plinko_status.fee_treasury;
+    plinko_status.fee_treasury = fee_treasury;
+
+    msg!("Fee treasury updated");
+    msg!("Old fee treasury: {}", old_fee_treasury);
+    msg!("New fee treasury: {}", fee_treasury);
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use crate::account::*;
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetGameRetention<'info> {
+    #[account(
+        mut,
+        seeds = [b"plinko_status"],
+        bump,
+        constraint = plinko_status.has_role(
+            &authority.key(),
+            roles.as_deref(),
+            Role::Operator
+        ) @ PlinkoError::MissingRole
+    )]
+    pub plinko_status: Account<'info, PlinkoStatus>,
+
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetGameRetention>, game_retention: i64) -> Result<()> {
+    require!(game_retention >= 0, PlinkoError::InvalidGameRetention);
+
+    let plinko_status = &mut ctx.accounts.plinko_status;
+    plinko_status.game_retention = game_retention;
+
+    msg!("Game retention updated to {} seconds", game_retention);
+
+    Ok(())
+}
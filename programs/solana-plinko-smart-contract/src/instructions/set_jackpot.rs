@@ -0,0 +1,41 @@
+use crate::account::*;
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetJackpot<'info> {
+    #[account(
+        mut,
+        seeds = [b"plinko_status"],
+        bump,
+        constraint = plinko_status.has_role(
+            &authority.key(),
+            roles.as_deref(),
+            Role::Operator
+        ) @ PlinkoError::MissingRole,
+        constraint = !plinko_status.is_timelocked() @ PlinkoError::ChangeTimelocked
+    )]
+    pub plinko_status: Account<'info, PlinkoStatus>,
+
+    // Bets can only contribute once the pool exists
+    #[account(seeds = [b"jackpot"], bump = jackpot.bump)]
+    pub jackpot: Box<Account<'info, Jackpot>>,
+
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetJackpot>, jackpot_fee: u64, jackpot_odds: u64) -> Result<()> {
+    (ParameterChange::Jackpot { jackpot_fee, jackpot_odds }).validate()?;
+
+    let plinko_status = &mut ctx.accounts.plinko_status;
+    plinko_status.jackpot_fee = jackpot_fee;
+    plinko_status.jackpot_odds = jackpot_odds;
+
+    msg!("Jackpot fee updated to {} basis points", jackpot_fee);
+    msg!("Jackpot odds: 1 in {}", jackpot_odds);
+
+    Ok(())
+}
@@ -8,15 +8,23 @@ pub struct SetMaxBalls<'info> {
         mut,
         seeds = [b"plinko_status"],
         bump,
-        constraint = plinko_status.is_owner(&authority.key()) @ PlinkoError::OnlyOwner
+        constraint = plinko_status.has_role(
+            &authority.key(),
+            roles.as_deref(),
+            Role::Operator
+        ) @ PlinkoError::MissingRole,
+        constraint = !plinko_status.is_timelocked() @ PlinkoError::ChangeTimelocked
     )]
     pub plinko_status: Account<'info, PlinkoStatus>,
 
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
+
     pub authority: Signer<'info>,
 }
 
 pub fn handler(ctx: Context<SetMaxBalls>, new_max_balls: u8) -> Result<()> {
-    require!(new_max_balls <= 100, PlinkoError::MaxBallsTooHigh);
+    ParameterChange::MaxBalls(new_max_balls).validate()?;
 
     let plinko_status = &mut ctx.accounts.plinko_status;
     plinko_status.max_balls = new_max_balls;
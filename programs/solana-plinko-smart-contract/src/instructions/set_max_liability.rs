@@ -0,0 +1,36 @@
+use crate::account::*;
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetMaxLiability<'info> {
+    #[account(
+        seeds = [b"plinko_status"],
+        bump,
+        constraint = plinko_status.has_role(
+            &authority.key(),
+            roles.as_deref(),
+            Role::Operator
+        ) @ PlinkoError::MissingRole
+    )]
+    pub plinko_status: Account<'info, PlinkoStatus>,
+
+    #[account(mut, seeds = [b"house"], bump)]
+    pub house: Account<'info, House>,
+
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetMaxLiability>, max_liability_bps: u64) -> Result<()> {
+    require!(max_liability_bps > 0 && max_liability_bps <= 10_000, PlinkoError::InvalidValue);
+
+    let house = &mut ctx.accounts.house;
+    house.max_liability_bps = max_liability_bps;
+
+    msg!("Maximum liability updated to {} basis points", max_liability_bps);
+
+    Ok(())
+}
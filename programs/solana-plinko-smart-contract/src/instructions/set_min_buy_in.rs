@@ -8,15 +8,23 @@ pub struct SetMinBuyIn<'info> {
         mut,
         seeds = [b"plinko_status"],
         bump,
-        constraint = plinko_status.is_owner(&authority.key()) @ PlinkoError::OnlyOwner
+        constraint = plinko_status.has_role(
+            &authority.key(),
+            roles.as_deref(),
+            Role::Operator
+        ) @ PlinkoError::MissingRole,
+        constraint = !plinko_status.is_timelocked() @ PlinkoError::ChangeTimelocked
     )]
     pub plinko_status: Account<'info, PlinkoStatus>,
 
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
+
     pub authority: Signer<'info>,
 }
 
 pub fn handler(ctx: Context<SetMinBuyIn>, new_min_buy_in: u64) -> Result<()> {
-    require!(new_min_buy_in > 0, PlinkoError::InvalidValue);
+    ParameterChange::MinBuyIn(new_min_buy_in).validate()?;
 
     let plinko_status = &mut ctx.accounts.plinko_status;
     plinko_status.min_buy_in = new_min_buy_in;
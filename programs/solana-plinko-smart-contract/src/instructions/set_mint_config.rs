@@ -0,0 +1,51 @@
+use crate::account::*;
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetMintConfig<'info> {
+    #[account(
+        seeds = [b"plinko_status"],
+        bump,
+        constraint = plinko_status.has_role(
+            &authority.key(),
+            roles.as_deref(),
+            Role::Operator
+        ) @ PlinkoError::MissingRole,
+        constraint = !plinko_status.is_timelocked() @ PlinkoError::ChangeTimelocked
+    )]
+    pub plinko_status: Account<'info, PlinkoStatus>,
+
+    #[account(
+        mut,
+        seeds = [b"mint_config", mint_config.mint.as_ref()],
+        bump = mint_config.bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<SetMintConfig>,
+    platform_fee: u64,
+    min_buy_in: u64,
+    max_liability_bps: u64,
+    enabled: bool
+) -> Result<()> {
+    MintConfig::validate(platform_fee, min_buy_in, max_liability_bps)?;
+
+    let mint_config = &mut ctx.accounts.mint_config;
+    mint_config.configure(platform_fee, min_buy_in, max_liability_bps, enabled);
+
+    msg!("Mint {} config updated", mint_config.mint);
+    msg!("Platform Fee: {} basis points", platform_fee);
+    msg!("Min Buy-in: {}", min_buy_in);
+    msg!("Max liability: {} basis points", max_liability_bps);
+    msg!("Enabled: {}", enabled);
+
+    Ok(())
+}
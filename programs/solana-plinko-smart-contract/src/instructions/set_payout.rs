@@ -3,40 +3,60 @@ use crate::errors::*;
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
+#[instruction(risk_level: RiskLevel, rows: u8)]
 pub struct SetPayout<'info> {
     #[account(
-    mut,
     seeds = [b"plinko_status"],
     bump,
-    constraint = plinko_status.is_owner(&authority.key()) @ PlinkoError::OnlyOwner,
-    constraint = !plinko_status.odds_locked @ PlinkoError::OddsLocked
+    constraint = plinko_status.has_role(
+        &authority.key(),
+        roles.as_deref(),
+        Role::OddsManager
+    ) @ PlinkoError::MissingRole,
+    constraint = !plinko_status.odds_locked @ PlinkoError::OddsLocked,
+    constraint = !plinko_status.is_timelocked() @ PlinkoError::ChangeTimelocked
   )]
     pub plinko_status: Account<'info, PlinkoStatus>,
 
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + PayoutTable::LEN,
+        seeds = [b"payout_table".as_ref(), &[risk_level as u8], &[rows]],
+        bump,
+        constraint = !payout_table.locked @ PlinkoError::PayoutTableLocked
+    )]
+    pub payout_table: Account<'info, PayoutTable>,
+
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
+
+    #[account(mut)]
     pub authority: Signer<'info>,
-}
-
-pub fn handler(ctx: Context<SetPayout>, payouts: Vec<u64>, bucket_weights: Vec<u64>) -> Result<()> {
-    require!(bucket_weights.len() == payouts.len(), PlinkoError::InvalidLength);
-    require!(!bucket_weights.is_empty(), PlinkoError::InvalidLength);
-    require!(bucket_weights.len() <= 100, PlinkoError::InvalidLength);
-
-    for i in 1..bucket_weights.len() {
-        require!(bucket_weights[i] > bucket_weights[i - 1], PlinkoError::InvalidBucketIndex);
-    }
 
-    for payout in &payouts {
-        require!(*payout <= 10_000_000, PlinkoError::InvalidBucketIndex); // Max 100x
-    }
+    pub system_program: Program<'info, System>,
+}
 
-    let plinko_status = &mut ctx.accounts.plinko_status;
+pub fn handler(
+    ctx: Context<SetPayout>,
+    risk_level: RiskLevel,
+    rows: u8,
+    bucket_weights: Vec<u64>,
+    payouts: Vec<u64>
+) -> Result<()> {
+    PayoutTable::validate(rows, &bucket_weights, &payouts)?;
 
-    plinko_status.bucket_weights = bucket_weights;
-    plinko_status.payouts = payouts;
+    let payout_table = &mut ctx.accounts.payout_table;
+    payout_table.configure(risk_level, rows, bucket_weights, payouts, ctx.bumps.payout_table);
 
     msg!("Payouts updated successfully");
-    msg!("Number of buckets: {}", plinko_status.bucket_weights.len());
-    msg!("Max bucket value: {}", plinko_status.max_bucket_value()?);
+    msg!("Risk level: {:?}", risk_level);
+    msg!("Number of buckets: {}", payout_table.payouts.len());
+    if rows == 0 {
+        msg!("Max bucket value: {}", payout_table.max_bucket_value()?);
+    } else {
+        msg!("Board rows: {}", rows);
+    }
 
     Ok(())
 }
@@ -0,0 +1,35 @@
+use crate::account::*;
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetReferralFee<'info> {
+    #[account(
+        mut,
+        seeds = [b"plinko_status"],
+        bump,
+        constraint = plinko_status.has_role(
+            &authority.key(),
+            roles.as_deref(),
+            Role::Operator
+        ) @ PlinkoError::MissingRole,
+        constraint = !plinko_status.is_timelocked() @ PlinkoError::ChangeTimelocked
+    )]
+    pub plinko_status: Account<'info, PlinkoStatus>,
+
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetReferralFee>, referral_fee: u64) -> Result<()> {
+    ParameterChange::ReferralFee(referral_fee).validate()?;
+
+    let plinko_status = &mut ctx.accounts.plinko_status;
+    plinko_status.referral_fee = referral_fee;
+
+    msg!("Referral fee updated to {} basis points of the platform fee", referral_fee);
+
+    Ok(())
+}
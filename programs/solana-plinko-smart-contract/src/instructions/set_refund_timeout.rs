@@ -0,0 +1,40 @@
+use crate::account::*;
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetRefundTimeout<'info> {
+    #[account(
+        mut,
+        seeds = [b"plinko_status"],
+        bump,
+        constraint = plinko_status.has_role(
+            &authority.key(),
+            roles.as_deref(),
+            Role::Operator
+        ) @ PlinkoError::MissingRole
+    )]
+    pub plinko_status: Account<'info, PlinkoStatus>,
+
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<SetRefundTimeout>,
+    refund_timeout: i64,
+    refund_platform_fee: bool
+) -> Result<()> {
+    require!(refund_timeout > 0, PlinkoError::InvalidRefundTimeout);
+
+    let plinko_status = &mut ctx.accounts.plinko_status;
+    plinko_status.refund_timeout = refund_timeout;
+    plinko_status.refund_platform_fee = refund_platform_fee;
+
+    msg!("Refund timeout updated to {} seconds", refund_timeout);
+    msg!("Refund platform fee: {}", refund_platform_fee);
+
+    Ok(())
+}
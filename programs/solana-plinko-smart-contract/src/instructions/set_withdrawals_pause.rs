@@ -0,0 +1,42 @@
+use crate::account::*;
+use crate::errors::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetWithdrawalsPause<'info> {
+    #[account(
+        seeds = [b"plinko_status"],
+        bump,
+        constraint = plinko_status.has_role(
+            &authority.key(),
+            roles.as_deref(),
+            Role::Pauser
+        ) @ PlinkoError::MissingRole
+    )]
+    pub plinko_status: Account<'info, PlinkoStatus>,
+
+    #[account(
+        mut,
+        seeds = [b"house"],
+        bump
+    )]
+    pub house: Account<'info, House>,
+
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetWithdrawalsPause>, withdrawals_pause: bool) -> Result<()> {
+    let house = &mut ctx.accounts.house;
+    house.withdrawals_pause = withdrawals_pause;
+
+    if withdrawals_pause {
+        msg!("Withdrawals paused");
+    } else {
+        msg!("Withdrawals unpaused");
+    }
+
+    Ok(())
+}
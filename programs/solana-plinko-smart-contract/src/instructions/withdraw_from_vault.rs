@@ -8,10 +8,24 @@ pub struct WithdrawFromVault<'info> {
     #[account(
         seeds = [b"plinko_status"],
         bump,
-        constraint = plinko_status.is_owner(&authority.key()) @ PlinkoError::OnlyOwner
+        constraint = plinko_status.has_role(
+            &authority.key(),
+            roles.as_deref(),
+            Role::Treasurer
+        ) @ PlinkoError::MissingRole
     )]
     pub plinko_status: Account<'info, PlinkoStatus>,
 
+    #[account(
+        mut,
+        seeds = [b"house"],
+        bump
+    )]
+    pub house: Account<'info, House>,
+
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 
@@ -23,7 +37,7 @@ pub struct WithdrawFromVault<'info> {
 }
 
 pub fn handler(ctx: Context<WithdrawFromVault>, amount: u64) -> Result<()> {
-    require!(ctx.accounts.vault.lamports() >= amount, PlinkoError::InsufficientFunds);
+    ctx.accounts.house.check_withdrawal(amount, ctx.accounts.vault.lamports())?;
     let vault_bump = ctx.bumps.vault;
 
     sol_transfer_with_signer(
@@ -34,7 +48,11 @@ pub fn handler(ctx: Context<WithdrawFromVault>, amount: u64) -> Result<()> {
         amount
     )?;
 
+    let house = &mut ctx.accounts.house;
+    house.balance = ctx.accounts.vault.lamports();
+
     msg!("Withdrew {} lamports from Vault", amount);
+    msg!("House balance: {} lamports", house.balance);
 
     Ok(())
 }
@@ -0,0 +1,86 @@
+use crate::account::*;
+use crate::utils::*;
+use crate::errors::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{ Mint, TokenAccount, TokenInterface };
+
+#[derive(Accounts)]
+pub struct WithdrawTokenFromVault<'info> {
+    #[account(
+        seeds = [b"plinko_status"],
+        bump,
+        constraint = plinko_status.has_role(
+            &authority.key(),
+            roles.as_deref(),
+            Role::Treasurer
+        ) @ PlinkoError::MissingRole
+    )]
+    pub plinko_status: Account<'info, PlinkoStatus>,
+
+    #[account(
+        seeds = [b"house"],
+        bump,
+        constraint = !house.withdrawals_pause @ PlinkoError::WithdrawalsPaused
+    )]
+    pub house: Account<'info, House>,
+
+    #[account(
+        mut,
+        seeds = [b"mint_config", mint.key().as_ref()],
+        bump = mint_config.bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(seeds = [b"roles"], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = authority,
+        token::token_program = token_program
+    )]
+    pub authority_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account(seeds = [b"vaultseed"], bump)]
+    pub vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"token_vault", mint.key().as_ref()],
+        bump = mint_config.vault_bump
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<WithdrawTokenFromVault>, amount: u64) -> Result<()> {
+    ctx.accounts.mint_config.check_withdrawal(amount, ctx.accounts.vault_token_account.amount)?;
+    let vault_bump = ctx.bumps.vault;
+
+    token_transfer_with_signer(
+        ctx.accounts.vault_token_account.to_account_info(),
+        ctx.accounts.authority_token_account.to_account_info(),
+        ctx.accounts.vault.to_account_info(),
+        ctx.accounts.mint.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        &[&[b"vaultseed", &[vault_bump]]],
+        amount,
+        ctx.accounts.mint.decimals
+    )?;
+
+    ctx.accounts.vault_token_account.reload()?;
+    ctx.accounts.mint_config.balance = ctx.accounts.vault_token_account.amount;
+
+    msg!("Withdrew {} of mint {} from Vault", amount, ctx.accounts.mint.key());
+    msg!("Mint balance: {}", ctx.accounts.mint_config.balance);
+
+    Ok(())
+}
@@ -0,0 +1,179 @@
+use crate::account::*;
+use anchor_lang::prelude::*;
+
+// Layouts `migrate` can still read. Version 1 is the layout deployed before versioning: it has
+// no version byte, so it is told apart by its size (`8 + LEN` of the old layout).
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct HouseV1 {
+    pub owner: Pubkey,
+    pub balance: u64,
+    pub maximum_payout: u64, // Replaced by the exposure limit
+    pub total_payout: u64,
+    pub withdrawals_pause: bool,
+    pub pending_request: u32,
+}
+
+impl HouseV1 {
+    pub const SPACE: usize = 77;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PlinkoStatusV1 {
+    pub owner: Pubkey,
+    pub platform_fee: u64,
+    pub fee_denominator: u64,
+    pub payout_denominator: u64,
+    pub min_buy_in: u64,
+    pub max_balls: u8,
+    pub odds_locked: bool,
+    pub paused: bool,
+    pub bucket_weights: Vec<u64>, // Moved to the payout tables
+    pub payouts: Vec<u64>, // Moved to the payout tables
+    pub total_games: u64,
+    pub total_volume: u64,
+    pub total_payouts: u64,
+    pub fee_treasury: Pubkey,
+    pub house_account: Pubkey,
+    pub force: [u8; 32], // Moved to the game
+    pub status: u8, // Moved to the game
+}
+
+impl PlinkoStatusV1 {
+    pub const SPACE: usize = 972;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct GameV1 {
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub bet_amount: u64,
+    pub amount_for_house: u64,
+    pub num_balls: u8,
+    pub bet_amount_per_ball: u64,
+    pub buckets: Vec<u8>,
+    pub payout: u64,
+    pub has_ended: bool,
+    pub request_id: u64,
+    pub created_at: i64,
+    pub ended_at: i64,
+}
+
+impl GameV1 {
+    pub const SPACE: usize = 178;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UserStatsV1 {
+    pub user: Pubkey,
+    pub total_games: u64,
+    pub total_wagered: u64,
+    pub total_won: u64,
+    pub game_ids: Vec<u64>, // Replaced by per-player nonces and history pages
+}
+
+impl UserStatsV1 {
+    pub const SPACE: usize = 876;
+}
+
+impl From<HouseV1> for House {
+    fn from(v1: HouseV1) -> Self {
+        House {
+            version: House::VERSION,
+            owner: v1.owner,
+            balance: v1.balance,
+            max_liability_bps: 10_000, // Pending liabilities fully backed, as on a new deployment
+            total_payout: v1.total_payout,
+            withdrawals_pause: v1.withdrawals_pause,
+            pending_request: v1.pending_request,
+            pending_liability: 0,
+            lp_enabled: false,
+            unclaimed_rakeback: 0,
+            fee_refunds_owed: 0,
+        }
+    }
+}
+
+impl From<PlinkoStatusV1> for PlinkoStatus {
+    fn from(v1: PlinkoStatusV1) -> Self {
+        PlinkoStatus {
+            version: PlinkoStatus::VERSION,
+            owner: v1.owner,
+            platform_fee: v1.platform_fee,
+            fee_denominator: v1.fee_denominator,
+            payout_denominator: v1.payout_denominator,
+            min_buy_in: v1.min_buy_in,
+            max_balls: v1.max_balls,
+            odds_locked: v1.odds_locked,
+            paused: v1.paused,
+            total_games: v1.total_games,
+            total_volume: v1.total_volume,
+            total_payouts: v1.total_payouts,
+            fee_treasury: v1.fee_treasury,
+            house_account: v1.house_account,
+            refund_timeout: PlinkoStatus::DEFAULT_REFUND_TIMEOUT,
+            refund_platform_fee: false,
+            game_retention: PlinkoStatus::DEFAULT_GAME_RETENTION,
+            pending_owner: Pubkey::default(),
+            change_delay: 0,
+            next_change_id: 0,
+            referral_fee: 0,
+            max_integrator_fee: 0,
+            fee_model: FeeModel::Stake,
+            winnings_fee: 0,
+            jackpot_fee: 0,
+            jackpot_odds: 0,
+        }
+    }
+}
+
+impl From<GameV1> for Game {
+    fn from(v1: GameV1) -> Self {
+        Game {
+            version: Game::VERSION,
+            nonce: v1.game_id,
+            player: v1.player,
+            mint: Pubkey::default(),
+            bet_amount: v1.bet_amount,
+            amount_for_house: v1.amount_for_house,
+            platform_fee: v1.bet_amount.saturating_sub(v1.amount_for_house),
+            num_balls: v1.num_balls,
+            risk_level: RiskLevel::Medium, // The single payout table of the old layout
+            rows: 0,
+            bet_amount_per_ball: v1.bet_amount_per_ball,
+            buckets: v1.buckets,
+            paths: Vec::new(),
+            payout: v1.payout,
+            max_payout: 0,
+            status: Status::Settled, // Pending games are refunded by `close_legacy_game` instead
+            request_id: v1.request_id,
+            force: [0; 32],
+            random: Pubkey::default(),
+            created_at: v1.created_at,
+            expires_at: v1.created_at + PlinkoStatus::DEFAULT_REFUND_TIMEOUT,
+            ended_at: v1.ended_at,
+            jackpot_contribution: 0,
+            jackpot_payout: 0,
+            referral_fee: 0,
+            fee_discount: 0,
+            rakeback: 0,
+        }
+    }
+}
+
+impl From<UserStatsV1> for UserStats {
+    fn from(v1: UserStatsV1) -> Self {
+        UserStats {
+            version: UserStats::VERSION,
+            user: v1.user,
+            total_games: v1.total_games,
+            total_wagered: v1.total_wagered,
+            total_won: v1.total_won,
+            next_nonce: 0,
+            recent_games: [GameRecord::default(); UserStats::RECENT_GAMES],
+            referrer: Pubkey::default(),
+            rakeback_accrued: 0,
+            rakeback_claimed: 0,
+        }
+    }
+}
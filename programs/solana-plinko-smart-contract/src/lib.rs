@@ -1,16 +1,40 @@
+// The IDL handlers `#[program]` generates at the crate root still call the deprecated
+// `AccountInfo::realloc`. The modules below turn the lint back on for our own code.
+#![allow(deprecated)]
+
 use anchor_lang::prelude::*;
 
 declare_id!("7dzjQ2uoBb9dDC6S4bdAk7rynABaBWrXWaXkp4xBicuv");
 
+#[warn(deprecated)]
 pub mod account;
+#[warn(deprecated)]
 pub mod errors;
+#[warn(deprecated)]
+pub mod events;
+#[warn(deprecated)]
 pub mod instructions;
+#[warn(deprecated)]
+pub mod legacy;
+#[warn(deprecated)]
 pub mod misc;
+#[warn(deprecated)]
 pub mod utils;
 
+use crate::account::{
+    FeeModel,
+    FeeRecipient,
+    FeeTier,
+    MultisigAction,
+    ParameterChange,
+    Referral,
+    RiskLevel,
+    Role,
+};
 use crate::instructions::*;
 
 #[program]
+#[warn(deprecated)]
 pub mod solana_plinko_smart_contract {
     use super::*;
 
@@ -25,33 +49,118 @@ pub mod solana_plinko_smart_contract {
 
     pub fn set_payout(
         ctx: Context<SetPayout>,
+        risk_level: RiskLevel,
+        rows: u8,
         bucket_weights: Vec<u64>,
         payouts: Vec<u64>
     ) -> Result<()> {
-        set_payout::handler(ctx, bucket_weights, payouts)
+        set_payout::handler(ctx, risk_level, rows, bucket_weights, payouts)
     }
 
     pub fn lock_odds(ctx: Context<LockOdds>) -> Result<()> {
         lock_odds::handler(ctx)
     }
 
+    pub fn lock_payout_table(
+        ctx: Context<LockPayoutTable>,
+        risk_level: RiskLevel,
+        rows: u8
+    ) -> Result<()> {
+        lock_payout_table::handler(ctx, risk_level, rows)
+    }
+
     pub fn play_game(
         ctx: Context<PlayGame>,
         force: [u8; 32],
-        game_id: u64,
         num_balls: u8,
+        risk_level: RiskLevel,
+        rows: u8,
+        user_bet_amount: u64,
+        integrator_fee: u64
+    ) -> Result<()> {
+        play_game::handler(
+            ctx,
+            force,
+            num_balls,
+            risk_level,
+            rows,
+            user_bet_amount,
+            integrator_fee
+        )
+    }
+
+    pub fn play_game_token(
+        ctx: Context<PlayGameToken>,
+        force: [u8; 32],
+        num_balls: u8,
+        risk_level: RiskLevel,
+        rows: u8,
         user_bet_amount: u64
     ) -> Result<()> {
-        play_game::handler(ctx, force, game_id, num_balls, user_bet_amount)
+        play_game_token::handler(ctx, force, num_balls, risk_level, rows, user_bet_amount)
     }
 
     pub fn fulfill_random_words(
         ctx: Context<FulFillRandomWords>,
         force: [u8; 32],
-        game_id: u64,
+        nonce: u64,
+        request_id: u64
+    ) -> Result<()> {
+        fulfill_random_words::handler(ctx, force, nonce, request_id)
+    }
+
+    pub fn fulfill_random_words_token(
+        ctx: Context<FulFillRandomWordsToken>,
+        force: [u8; 32],
+        nonce: u64,
         request_id: u64
     ) -> Result<()> {
-        fulfill_random_words::handler(ctx, force, game_id, request_id)
+        fulfill_random_words_token::handler(ctx, force, nonce, request_id)
+    }
+
+    pub fn refund_expired_game(ctx: Context<RefundExpiredGame>, nonce: u64) -> Result<()> {
+        refund_expired_game::handler(ctx, nonce)
+    }
+
+    pub fn refund_expired_game_token(
+        ctx: Context<RefundExpiredGameToken>,
+        nonce: u64
+    ) -> Result<()> {
+        refund_expired_game_token::handler(ctx, nonce)
+    }
+
+    pub fn cancel_game(ctx: Context<CancelGame>, nonce: u64) -> Result<()> {
+        cancel_game::handler(ctx, nonce)
+    }
+
+    pub fn cancel_game_token(ctx: Context<CancelGameToken>, nonce: u64) -> Result<()> {
+        cancel_game_token::handler(ctx, nonce)
+    }
+
+    pub fn close_game(ctx: Context<CloseGame>, nonce: u64) -> Result<()> {
+        close_game::handler(ctx, nonce)
+    }
+
+    pub fn close_games<'info>(ctx: Context<'_, '_, 'info, 'info, CloseGames<'info>>) -> Result<()> {
+        close_games::handler(ctx)
+    }
+
+    pub fn close_legacy_game(ctx: Context<CloseLegacyGame>, game_id: u64) -> Result<()> {
+        close_legacy_game::handler(ctx, game_id)
+    }
+
+    pub fn add_mint(ctx: Context<AddMint>, platform_fee: u64, min_buy_in: u64) -> Result<()> {
+        add_mint::handler(ctx, platform_fee, min_buy_in)
+    }
+
+    pub fn set_mint_config(
+        ctx: Context<SetMintConfig>,
+        platform_fee: u64,
+        min_buy_in: u64,
+        max_liability_bps: u64,
+        enabled: bool
+    ) -> Result<()> {
+        set_mint_config::handler(ctx, platform_fee, min_buy_in, max_liability_bps, enabled)
     }
 
     pub fn set_platform_fee(ctx: Context<SetPlatformFee>, new_fee: u64) -> Result<()> {
@@ -66,11 +175,206 @@ pub mod solana_plinko_smart_contract {
         set_max_balls::handler(ctx, new_max_balls)
     }
 
+    pub fn set_fee_treasury(ctx: Context<SetFeeTreasury>, fee_treasury: Pubkey) -> Result<()> {
+        set_fee_treasury::handler(ctx, fee_treasury)
+    }
+
+    pub fn set_fee_model(
+        ctx: Context<SetFeeModel>,
+        fee_model: FeeModel,
+        winnings_fee: u64
+    ) -> Result<()> {
+        set_fee_model::handler(ctx, fee_model, winnings_fee)
+    }
+
+    pub fn set_fee_distribution(
+        ctx: Context<SetFeeDistribution>,
+        recipients: Vec<FeeRecipient>
+    ) -> Result<()> {
+        set_fee_distribution::handler(ctx, recipients)
+    }
+
+    pub fn distribute_fees<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributeFees<'info>>
+    ) -> Result<()> {
+        distribute_fees::handler(ctx)
+    }
+
+    pub fn distribute_token_fees<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributeTokenFees<'info>>
+    ) -> Result<()> {
+        distribute_token_fees::handler(ctx)
+    }
+
+    pub fn set_fee_tiers(ctx: Context<SetFeeTiers>, tiers: Vec<FeeTier>) -> Result<()> {
+        set_fee_tiers::handler(ctx, tiers)
+    }
+
+    pub fn claim_rakeback(ctx: Context<ClaimRakeback>) -> Result<()> {
+        claim_rakeback::handler(ctx)
+    }
+
+    pub fn create_jackpot(ctx: Context<CreateJackpot>) -> Result<()> {
+        create_jackpot::handler(ctx)
+    }
+
+    pub fn set_jackpot(ctx: Context<SetJackpot>, jackpot_fee: u64, jackpot_odds: u64) -> Result<()> {
+        set_jackpot::handler(ctx, jackpot_fee, jackpot_odds)
+    }
+
+    pub fn set_referral_fee(ctx: Context<SetReferralFee>, referral_fee: u64) -> Result<()> {
+        set_referral_fee::handler(ctx, referral_fee)
+    }
+
+    pub fn set_max_integrator_fee(
+        ctx: Context<SetMaxIntegratorFee>,
+        max_integrator_fee: u64
+    ) -> Result<()> {
+        set_max_integrator_fee::handler(ctx, max_integrator_fee)
+    }
+
+    pub fn register_integrator(ctx: Context<RegisterIntegrator>) -> Result<()> {
+        register_integrator::handler(ctx)
+    }
+
+    pub fn register_referrer(
+        ctx: Context<RegisterReferrer>,
+        code: [u8; Referral::CODE_LEN]
+    ) -> Result<()> {
+        register_referrer::handler(ctx, code)
+    }
+
+    pub fn bind_referrer(ctx: Context<BindReferrer>) -> Result<()> {
+        bind_referrer::handler(ctx)
+    }
+
+    pub fn claim_referral_earnings(ctx: Context<ClaimReferralEarnings>) -> Result<()> {
+        claim_referral_earnings::handler(ctx)
+    }
+
+    pub fn set_refund_timeout(
+        ctx: Context<SetRefundTimeout>,
+        refund_timeout: i64,
+        refund_platform_fee: bool
+    ) -> Result<()> {
+        set_refund_timeout::handler(ctx, refund_timeout, refund_platform_fee)
+    }
+
+    pub fn set_game_retention(ctx: Context<SetGameRetention>, game_retention: i64) -> Result<()> {
+        set_game_retention::handler(ctx, game_retention)
+    }
+
+    pub fn set_max_liability(ctx: Context<SetMaxLiability>, max_liability_bps: u64) -> Result<()> {
+        set_max_liability::handler(ctx, max_liability_bps)
+    }
+
+    pub fn propose_change(ctx: Context<ProposeChange>, change: ParameterChange) -> Result<()> {
+        propose_change::handler(ctx, change)
+    }
+
+    pub fn execute_change(ctx: Context<ExecuteChange>, id: u64) -> Result<()> {
+        execute_change::handler(ctx, id)
+    }
+
+    pub fn cancel_change(ctx: Context<CancelChange>, id: u64) -> Result<()> {
+        cancel_change::handler(ctx, id)
+    }
+
+    pub fn create_multisig(
+        ctx: Context<CreateMultisig>,
+        signers: Vec<Pubkey>,
+        threshold: u8
+    ) -> Result<()> {
+        create_multisig::handler(ctx, signers, threshold)
+    }
+
+    pub fn propose_multisig_transaction(
+        ctx: Context<ProposeMultisigTransaction>,
+        action: MultisigAction
+    ) -> Result<()> {
+        propose_multisig_transaction::handler(ctx, action)
+    }
+
+    pub fn approve_multisig_transaction(
+        ctx: Context<ApproveMultisigTransaction>,
+        id: u64
+    ) -> Result<()> {
+        approve_multisig_transaction::handler(ctx, id)
+    }
+
+    pub fn execute_multisig_transaction(
+        ctx: Context<ExecuteMultisigTransaction>,
+        id: u64
+    ) -> Result<()> {
+        execute_multisig_transaction::handler(ctx, id)
+    }
+
+    pub fn cancel_multisig_transaction(
+        ctx: Context<CancelMultisigTransaction>,
+        id: u64
+    ) -> Result<()> {
+        cancel_multisig_transaction::handler(ctx, id)
+    }
+
+    pub fn propose_owner(ctx: Context<ProposeOwner>, new_owner: Pubkey) -> Result<()> {
+        propose_owner::handler(ctx, new_owner)
+    }
+
+    pub fn accept_owner(ctx: Context<AcceptOwner>) -> Result<()> {
+        accept_owner::handler(ctx)
+    }
+
+    pub fn cancel_owner_transfer(ctx: Context<CancelOwnerTransfer>) -> Result<()> {
+        cancel_owner_transfer::handler(ctx)
+    }
+
+    pub fn grant_role(ctx: Context<GrantRole>, member: Pubkey, role: Role) -> Result<()> {
+        grant_role::handler(ctx, member, role)
+    }
+
+    pub fn revoke_role(ctx: Context<RevokeRole>, member: Pubkey, role: Role) -> Result<()> {
+        revoke_role::handler(ctx, member, role)
+    }
+
+    pub fn migrate(ctx: Context<Migrate>) -> Result<()> {
+        migrate::handler(ctx)
+    }
+
     pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
         set_paused::handler(ctx, paused)
     }
 
+    pub fn set_withdrawals_pause(
+        ctx: Context<SetWithdrawalsPause>,
+        withdrawals_pause: bool
+    ) -> Result<()> {
+        set_withdrawals_pause::handler(ctx, withdrawals_pause)
+    }
+
     pub fn withdraw_from_vault(ctx: Context<WithdrawFromVault>, amount: u64) -> Result<()> {
         withdraw_from_vault::handler(ctx, amount)
     }
+
+    pub fn enable_liquidity_pool(ctx: Context<EnableLiquidityPool>) -> Result<()> {
+        enable_liquidity_pool::handler(ctx)
+    }
+
+    pub fn deposit_liquidity(ctx: Context<DepositLiquidity>, amount: u64) -> Result<()> {
+        deposit_liquidity::handler(ctx, amount)
+    }
+
+    pub fn redeem_liquidity(ctx: Context<RedeemLiquidity>, shares: u64) -> Result<()> {
+        redeem_liquidity::handler(ctx, shares)
+    }
+
+    pub fn repay_fee_refunds(ctx: Context<RepayFeeRefunds>) -> Result<()> {
+        repay_fee_refunds::handler(ctx)
+    }
+
+    pub fn withdraw_token_from_vault(
+        ctx: Context<WithdrawTokenFromVault>,
+        amount: u64
+    ) -> Result<()> {
+        withdraw_token_from_vault::handler(ctx, amount)
+    }
 }
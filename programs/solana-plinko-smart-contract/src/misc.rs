@@ -24,8 +24,8 @@ pub fn current_state(randomness: &RandomnessAccountData) -> u64 {
     if let Some(randomness) = randomness.fulfilled_randomness() {
         let value = randomness[0..size_of::<u64>()].try_into().unwrap();
 
-        return u64::from_le_bytes(value);
+        u64::from_le_bytes(value)
     } else {
-        return 0;
+        0
     }
 }
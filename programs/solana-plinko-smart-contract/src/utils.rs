@@ -1,6 +1,27 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::{ program::invoke, system_instruction::transfer };
-use solana_program::program::invoke_signed;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{
+        transfer_fee::{ instruction::harvest_withheld_tokens_to_mint, TransferFeeAmount },
+        BaseStateWithExtensions,
+        ExtensionType,
+        StateWithExtensions,
+    },
+};
+use anchor_spl::token_interface::{
+    burn,
+    close_account,
+    mint_to,
+    transfer_checked,
+    Burn,
+    CloseAccount,
+    MintTo,
+    Token2022,
+    TransferChecked,
+};
+use crate::account::{ Multisig, MultisigAction, MultisigTransaction };
+use crate::errors::PlinkoError;
+use solana_program::program::{ invoke, invoke_signed };
 
 pub fn sol_transfer_with_signer<'a>(
     source: AccountInfo<'a>,
@@ -13,3 +34,184 @@ pub fn sol_transfer_with_signer<'a>(
     invoke_signed(&ix, &[source, destination, system_program], signers)?;
     Ok(())
 }
+
+pub fn token_transfer<'a>(
+    source: AccountInfo<'a>,
+    destination: AccountInfo<'a>,
+    authority: AccountInfo<'a>,
+    mint: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+    amount: u64,
+    decimals: u8
+) -> Result<()> {
+    let cpi_accounts = TransferChecked {
+        from: source,
+        mint,
+        to: destination,
+        authority,
+    };
+    transfer_checked(CpiContext::new(token_program, cpi_accounts), amount, decimals)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn token_transfer_with_signer<'a>(
+    source: AccountInfo<'a>,
+    destination: AccountInfo<'a>,
+    authority: AccountInfo<'a>,
+    mint: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+    signers: &[&[&[u8]]; 1],
+    amount: u64,
+    decimals: u8
+) -> Result<()> {
+    let cpi_accounts = TransferChecked {
+        from: source,
+        mint,
+        to: destination,
+        authority,
+    };
+    transfer_checked(
+        CpiContext::new_with_signer(token_program, cpi_accounts, signers),
+        amount,
+        decimals
+    )
+}
+
+pub fn token_mint_to_with_signer<'a>(
+    mint: AccountInfo<'a>,
+    destination: AccountInfo<'a>,
+    authority: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+    signers: &[&[&[u8]]; 1],
+    amount: u64
+) -> Result<()> {
+    let cpi_accounts = MintTo {
+        mint,
+        to: destination,
+        authority,
+    };
+    mint_to(CpiContext::new_with_signer(token_program, cpi_accounts, signers), amount)
+}
+
+pub fn token_burn<'a>(
+    mint: AccountInfo<'a>,
+    source: AccountInfo<'a>,
+    authority: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+    amount: u64
+) -> Result<()> {
+    let cpi_accounts = Burn {
+        mint,
+        from: source,
+        authority,
+    };
+    burn(CpiContext::new(token_program, cpi_accounts), amount)
+}
+
+pub fn token_close_with_signer<'a>(
+    account: AccountInfo<'a>,
+    destination: AccountInfo<'a>,
+    authority: AccountInfo<'a>,
+    mint: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+    signers: &[&[&[u8]]; 1]
+) -> Result<()> {
+    // Withheld transfer fees block closing, harvesting them to the mint is permissionless
+    if withheld_transfer_fees(&account)? > 0 {
+        let ix = harvest_withheld_tokens_to_mint(token_program.key, mint.key, &[account.key])?;
+        invoke(&ix, &[mint, account.clone(), token_program.clone()])?;
+    }
+
+    let cpi_accounts = CloseAccount {
+        account,
+        destination,
+        authority,
+    };
+    close_account(CpiContext::new_with_signer(token_program, cpi_accounts, signers))
+}
+
+fn withheld_transfer_fees(account: &AccountInfo) -> Result<u64> {
+    if *account.owner != Token2022::id() {
+        return Ok(0);
+    }
+
+    let account_data = account.try_borrow_data()?;
+    let account_state = StateWithExtensions::<spl_token_2022::state::Account>::unpack(
+        &account_data
+    )?;
+
+    Ok(
+        account_state
+            .get_extension::<TransferFeeAmount>()
+            .map(|fee_amount| u64::from(fee_amount.withheld_amount))
+            .unwrap_or(0)
+    )
+}
+
+pub fn check_mint_extensions(mint: &AccountInfo) -> Result<()> {
+    if *mint.owner != Token2022::id() {
+        return Ok(());
+    }
+
+    let mint_data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+
+    // Transfer fees are accounted for, but hooks need extra accounts and delegates can drain the vault
+    for extension in mint_state.get_extension_types()? {
+        require!(
+            !matches!(
+                extension,
+                ExtensionType::TransferHook |
+                    ExtensionType::PermanentDelegate |
+                    ExtensionType::NonTransferable |
+                    ExtensionType::ConfidentialTransferMint
+            ),
+            PlinkoError::UnsupportedMintExtension
+        );
+    }
+
+    Ok(())
+}
+
+// Owner-only instructions that need accounts of their own can't run inside
+// `execute_multisig_transaction`, once the multisig owns the program they take its approved
+// transaction in place of the owner's signature
+pub fn check_owner_or_multisig(
+    owner: &Pubkey,
+    authority: &Pubkey,
+    multisig: Option<&Account<Multisig>>,
+    transaction: Option<&Account<MultisigTransaction>>,
+    action: &MultisigAction
+) -> Result<()> {
+    match (multisig, transaction) {
+        (_, None) => {
+            require_keys_eq!(*authority, *owner, PlinkoError::OnlyOwner);
+            Ok(())
+        }
+        (Some(multisig), Some(transaction)) => {
+            require_keys_eq!(multisig.key(), *owner, PlinkoError::MultisigNotOwner);
+            multisig.check_approved(transaction, action)
+        }
+        (None, Some(_)) => err!(PlinkoError::MissingActionAccount),
+    }
+}
+
+// Tops an empty system account up to its rent-exempt minimum, so small transfers to it succeed
+pub fn fund_rent_exemption<'a>(
+    account: AccountInfo<'a>,
+    payer: AccountInfo<'a>,
+    system_program: AccountInfo<'a>
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(0);
+    let lamports = account.lamports();
+    if lamports < rent {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(system_program, anchor_lang::system_program::Transfer {
+                from: payer,
+                to: account,
+            }),
+            rent - lamports
+        )?;
+    }
+    Ok(())
+}
@@ -0,0 +1,67 @@
+import { Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { BN } from "bn.js";
+import { assert } from "chai";
+import { Plinko, expectError } from "./helpers";
+
+describe("Exposure limits", () => {
+  let plinko: Plinko;
+  let player: Keypair;
+
+  const BET = LAMPORTS_PER_SOL / 2;
+  const MAX_PAYOUT = 2 * (BET - 15_000_000); // 2x the stake after the 3% fee
+
+  const setMaxLiability = (maxLiabilityBps: number, signer = plinko.authority) =>
+    plinko.program.methods
+      .setMaxLiability(new BN(maxLiabilityBps))
+      .accountsStrict({
+        plinkoStatus: plinko.plinkoStatus,
+        house: plinko.house,
+        roles: null,
+        authority: signer.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+
+  const pendingLiability = async () =>
+    (await plinko.program.account.house.fetch(plinko.house)).pendingLiability.toNumber();
+
+  before(async () => {
+    plinko = await Plinko.start();
+    await plinko.setup(10 * LAMPORTS_PER_SOL);
+    player = plinko.wallet();
+  });
+
+  it("only lets operators change the limit", async () => {
+    await expectError(setMaxLiability(2_500, plinko.wallet()), "MissingRole");
+    await expectError(setMaxLiability(10_001), "InvalidValue");
+
+    // Pending games may take up to a quarter of the 10 SOL vault
+    await setMaxLiability(2_500);
+  });
+
+  it("reserves the worst-case payout of every bet", async () => {
+    await plinko.play(player, { bet: BET });
+    await plinko.play(player, { bet: BET });
+
+    assert.equal(await pendingLiability(), 2 * MAX_PAYOUT);
+  });
+
+  it("rejects bets past the limit", async () => {
+    await expectError(plinko.play(player, { bet: BET }), "ExposureLimitExceeded");
+    assert.equal(await pendingLiability(), 2 * MAX_PAYOUT);
+  });
+
+  it("releases the reservation when a game settles", async () => {
+    await plinko.fulfill(player.publicKey, 0);
+    assert.equal(await pendingLiability(), MAX_PAYOUT);
+
+    await plinko.play(player, { bet: BET });
+    assert.equal(await pendingLiability(), 2 * MAX_PAYOUT);
+  });
+
+  it("releases the reservation when a game is refunded", async () => {
+    await plinko.warp(60 * 60);
+    await plinko.refund(player, 1);
+    assert.equal(await pendingLiability(), MAX_PAYOUT);
+  });
+});
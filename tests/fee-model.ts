@@ -0,0 +1,141 @@
+import { Keypair, LAMPORTS_PER_SOL, SystemProgram } from "@solana/web3.js";
+import { BN } from "bn.js";
+import { assert } from "chai";
+import { Plinko, expectError, referralCode } from "./helpers";
+
+describe("Fee model", () => {
+  let plinko: Plinko;
+  let player: Keypair;
+  let escrowRent: number;
+
+  const BET = LAMPORTS_PER_SOL;
+
+  const setFeeModel = (feeModel: object, winningsFee: number, signer = plinko.authority) =>
+    plinko.program.methods
+      .setFeeModel(feeModel as any, new BN(winningsFee))
+      .accountsStrict({
+        plinkoStatus: plinko.plinkoStatus,
+        roles: null,
+        authority: signer.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+
+  // Balances of the player and the treasury around a settled game
+  const settle = async (risk: "low" | "medium" = "medium") => {
+    const { nonce } = await plinko.play(player, { bet: BET, risk });
+    const playerBefore = await plinko.balance(player.publicKey);
+    const treasuryBefore = await plinko.balance(plinko.feeTreasury.publicKey);
+    await plinko.fulfill(player.publicKey, nonce);
+
+    const game = await plinko.program.account.game.fetch(
+      plinko.gamePda(player.publicKey, nonce)
+    );
+    return {
+      paid: (await plinko.balance(player.publicKey)) - playerBefore - escrowRent,
+      fee: (await plinko.balance(plinko.feeTreasury.publicKey)) - treasuryBefore,
+      game,
+    };
+  };
+
+  before(async () => {
+    plinko = await Plinko.start();
+    await plinko.setup();
+    await plinko.setPayout(50, "low");
+    player = plinko.wallet();
+    escrowRent = await plinko.rentExempt(0);
+  });
+
+  it("only lets operators set a winnings fee of up to 10%", async () => {
+    await expectError(setFeeModel({ winnings: {} }, 500, plinko.wallet()), "MissingRole");
+    await expectError(setFeeModel({ winnings: {} }, 1_001), "WinningsFeeTooHigh");
+    await setFeeModel({ winnings: {} }, 500);
+  });
+
+  it("charges nothing up front under the winnings model", async () => {
+    const treasuryBefore = await plinko.balance(plinko.feeTreasury.publicKey);
+    const { nonce, escrow } = await plinko.play(player, { bet: BET });
+
+    assert.equal(await plinko.balance(plinko.feeTreasury.publicKey), treasuryBefore);
+    assert.equal(await plinko.balance(escrow), BET + escrowRent);
+    await plinko.fulfill(player.publicKey, nonce);
+  });
+
+  it("keeps the winnings fee from a winning payout", async () => {
+    const { paid, fee, game } = await settle();
+
+    // 2 SOL back on a 1 SOL bet, 5% of the 1 SOL won goes to the treasury
+    assert.equal(fee, 50_000_000);
+    assert.equal(paid, 2 * BET - 50_000_000);
+    assert.equal(game.payout.toNumber(), paid);
+    assert.equal(game.platformFee.toNumber(), fee);
+  });
+
+  it("charges no winnings fee on a loss", async () => {
+    const { paid, fee } = await settle("low");
+
+    assert.equal(fee, 0);
+    assert.equal(paid, BET / 2);
+  });
+
+  it("charges both fees under the hybrid model", async () => {
+    await setFeeModel({ hybrid: {} }, 500);
+    const treasuryBefore = await plinko.balance(plinko.feeTreasury.publicKey);
+    const { paid, fee, game } = await settle();
+
+    // 3% up front, then 5% of the 0.94 SOL won on the 0.97 SOL stake
+    assert.equal(
+      await plinko.balance(plinko.feeTreasury.publicKey),
+      treasuryBefore + 30_000_000 + fee
+    );
+    assert.equal(fee, 47_000_000);
+    assert.equal(paid, 1_940_000_000 - 47_000_000);
+    assert.equal(game.platformFee.toNumber(), 30_000_000 + 47_000_000);
+  });
+
+  it("gives the referrer its share of the winnings fee", async () => {
+    await setFeeModel({ winnings: {} }, 500);
+    await plinko.program.methods
+      .setReferralFee(new BN(2_000))
+      .accountsStrict({
+        plinkoStatus: plinko.plinkoStatus,
+        roles: null,
+        authority: plinko.authority.publicKey,
+      })
+      .signers([plinko.authority])
+      .rpc();
+
+    const referrer = plinko.wallet();
+    const referral = plinko.referralPda("winnings");
+    await plinko.program.methods
+      .registerReferrer(Array.from(referralCode("winnings")))
+      .accountsStrict({
+        referral,
+        referrer: referrer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([referrer])
+      .rpc();
+    await plinko.program.methods
+      .bindReferrer()
+      .accountsStrict({
+        userStats: plinko.userStatsPda(player.publicKey),
+        referral,
+        player: player.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([player])
+      .rpc();
+
+    const referralBefore = await plinko.balance(referral);
+    const { paid, fee } = await settle();
+
+    // 20% of the 50_000_000 winnings fee goes to the referrer
+    assert.equal(await plinko.balance(referral), referralBefore + 10_000_000);
+    assert.equal(fee, 40_000_000);
+    assert.equal(paid, 2 * BET - 50_000_000);
+
+    const account = await plinko.program.account.referral.fetch(referral);
+    assert.equal(account.unclaimed.toNumber(), 10_000_000);
+  });
+});
@@ -0,0 +1,112 @@
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram } from "@solana/web3.js";
+import { BN } from "bn.js";
+import { assert } from "chai";
+import { Plinko, expectError } from "./helpers";
+
+describe("Fee split", () => {
+  let plinko: Plinko;
+  let player: Keypair;
+  let team: PublicKey;
+  let partner: PublicKey;
+  let feeVaultRent: number;
+
+  const BET = LAMPORTS_PER_SOL;
+  const FEE = 30_000_000; // 3% of the bet
+
+  const split = (teamBps: number, partnerBps: number) => [
+    { recipient: team, bps: new BN(teamBps) },
+    { recipient: partner, bps: new BN(partnerBps) },
+  ];
+
+  const setFeeDistribution = (recipients: object[], signer = plinko.authority) =>
+    plinko.program.methods
+      .setFeeDistribution(recipients as any)
+      .accountsStrict({
+        plinkoStatus: plinko.plinkoStatus,
+        feeDistribution: plinko.feeDistribution,
+        feeVault: plinko.feeVault,
+        roles: null,
+        authority: signer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc();
+
+  const distribute = (recipients: PublicKey[]) =>
+    plinko.program.methods
+      .distributeFees()
+      .accountsStrict({
+        feeDistribution: plinko.feeDistribution,
+        feeVault: plinko.feeVault,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(
+        recipients.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true }))
+      )
+      .rpc();
+
+  before(async () => {
+    plinko = await Plinko.start();
+    await plinko.setup();
+    player = plinko.wallet();
+    team = plinko.wallet().publicKey;
+    partner = plinko.wallet().publicKey;
+    feeVaultRent = await plinko.rentExempt(0);
+  });
+
+  it("only lets treasurers set a split that adds up", async () => {
+    await expectError(setFeeDistribution(split(7_000, 3_000), plinko.wallet()), "MissingRole");
+    await expectError(setFeeDistribution(split(7_000, 2_000)), "InvalidFeeRecipients");
+  });
+
+  it("collects platform fees in the fee vault", async () => {
+    await setFeeDistribution(split(7_000, 3_000));
+    assert.ok((await plinko.feeTreasuryKey()).equals(plinko.feeVault));
+    assert.equal(await plinko.balance(plinko.feeVault), feeVaultRent);
+
+    await plinko.play(player, { bet: BET });
+    assert.equal(await plinko.balance(plinko.feeVault), feeVaultRent + FEE);
+  });
+
+  it("pays every recipient its share", async () => {
+    await expectError(distribute([partner, team]), "InvalidFeeRecipientAccount");
+    await expectError(distribute([team]), "InvalidFeeRecipientAccount");
+
+    const teamBefore = await plinko.balance(team);
+    const partnerBefore = await plinko.balance(partner);
+    await distribute([team, partner]);
+
+    assert.equal(await plinko.balance(team), teamBefore + 21_000_000);
+    assert.equal(await plinko.balance(partner), partnerBefore + 9_000_000);
+    assert.equal(await plinko.balance(plinko.feeVault), feeVaultRent);
+
+    const feeDistribution = await plinko.program.account.feeDistribution.fetch(
+      plinko.feeDistribution
+    );
+    assert.equal(feeDistribution.totalDistributed.toNumber(), FEE);
+  });
+
+  it("leaves the fee vault rent in place", async () => {
+    await plinko.warp(1); // New slot, so the same instruction is not a duplicate
+    await expectError(distribute([team, partner]), "NothingToDistribute");
+  });
+
+  it("applies a queued split once the delay has passed", async () => {
+    await plinko.executeChange(
+      await plinko.proposeChange({ changeDelay: { 0: new BN(60 * 60) } })
+    );
+    await expectError(setFeeDistribution(split(5_000, 5_000)), "ChangeTimelocked");
+
+    const id = await plinko.proposeChange({ feeDistribution: { 0: split(5_000, 5_000) } });
+    await plinko.warp(60 * 60);
+    await plinko.executeChange(id, { feeDistribution: true });
+
+    await plinko.play(player, { bet: BET });
+    const teamBefore = await plinko.balance(team);
+    const partnerBefore = await plinko.balance(partner);
+    await distribute([team, partner]);
+
+    assert.equal(await plinko.balance(team), teamBefore + FEE / 2);
+    assert.equal(await plinko.balance(partner), partnerBefore + FEE / 2);
+  });
+});
@@ -0,0 +1,162 @@
+import { Keypair, LAMPORTS_PER_SOL, SystemProgram } from "@solana/web3.js";
+import { BN } from "bn.js";
+import { assert } from "chai";
+import { Plinko, expectError } from "./helpers";
+
+describe("Fee tiers and rakeback", () => {
+  let plinko: Plinko;
+  let player: Keypair;
+
+  const BET = LAMPORTS_PER_SOL;
+  const FEE = 30_000_000; // 3% of the bet
+  const RAKEBACK = 10_000_000; // 1% of the bet
+
+  const tier = (minWagered: number, feeDiscount: number, rakeback: number) => ({
+    minWagered: new BN(minWagered),
+    feeDiscount: new BN(feeDiscount),
+    rakeback: new BN(rakeback),
+  });
+
+  const setFeeTiers = (tiers: object[], signer = plinko.authority) =>
+    plinko.program.methods
+      .setFeeTiers(tiers as any)
+      .accountsStrict({
+        plinkoStatus: plinko.plinkoStatus,
+        feeTiers: plinko.feeTiers,
+        roles: null,
+        authority: signer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc();
+
+  const claimRakeback = () =>
+    plinko.program.methods
+      .claimRakeback()
+      .accountsStrict({
+        userStats: plinko.userStatsPda(player.publicKey),
+        house: plinko.house,
+        vault: plinko.vault,
+        player: player.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([player])
+      .rpc();
+
+  const userStats = () =>
+    plinko.program.account.userStats.fetch(plinko.userStatsPda(player.publicKey));
+
+  const unclaimedRakeback = async () =>
+    (await plinko.program.account.house.fetch(plinko.house)).unclaimedRakeback.toNumber();
+
+  before(async () => {
+    plinko = await Plinko.start();
+    await plinko.setup();
+    player = plinko.wallet();
+  });
+
+  it("only lets operators set valid tiers", async () => {
+    await expectError(setFeeTiers([tier(BET, 5_000, 100)], plinko.wallet()), "MissingRole");
+    await expectError(setFeeTiers([tier(BET, 5_000, 501)]), "InvalidFeeTiers");
+    await expectError(
+      setFeeTiers([tier(BET, 5_000, 100), tier(BET, 6_000, 100)]),
+      "InvalidFeeTiers"
+    );
+
+    // Half the platform fee and 1% rakeback once a player has wagered 1 SOL
+    await setFeeTiers([tier(BET, 5_000, 100)]);
+  });
+
+  it("charges the full fee below the first tier", async () => {
+    const treasuryBefore = await plinko.balance(plinko.feeTreasury.publicKey);
+    const { nonce, game } = await plinko.play(player, { bet: BET });
+    await plinko.fulfill(player.publicKey, nonce);
+
+    assert.equal(
+      await plinko.balance(plinko.feeTreasury.publicKey),
+      treasuryBefore + FEE
+    );
+    assert.equal((await plinko.program.account.game.fetch(game)).rakeback.toNumber(), 0);
+    assert.equal((await userStats()).rakebackAccrued.toNumber(), 0);
+  });
+
+  it("discounts the fee and credits rakeback once the tier is reached", async () => {
+    const treasuryBefore = await plinko.balance(plinko.feeTreasury.publicKey);
+    const { nonce, game } = await plinko.play(player, { bet: BET });
+
+    assert.equal(
+      await plinko.balance(plinko.feeTreasury.publicKey),
+      treasuryBefore + FEE / 2
+    );
+    const gameAccount = await plinko.program.account.game.fetch(game);
+    assert.equal(gameAccount.amountForHouse.toNumber(), BET - FEE / 2);
+    assert.equal(gameAccount.rakeback.toNumber(), RAKEBACK);
+
+    // Rakeback is only credited once the game settles
+    assert.equal((await userStats()).rakebackAccrued.toNumber(), 0);
+    await plinko.fulfill(player.publicKey, nonce);
+    assert.equal((await userStats()).rakebackAccrued.toNumber(), RAKEBACK);
+    assert.equal(await unclaimedRakeback(), RAKEBACK);
+  });
+
+  it("pays the rakeback out of the vault", async () => {
+    const playerBefore = await plinko.balance(player.publicKey);
+    const vaultBefore = await plinko.balance(plinko.vault);
+    await claimRakeback();
+
+    assert.equal(await plinko.balance(player.publicKey), playerBefore + RAKEBACK);
+    assert.equal(await plinko.balance(plinko.vault), vaultBefore - RAKEBACK);
+
+    const stats = await userStats();
+    assert.equal(stats.rakebackAccrued.toNumber(), 0);
+    assert.equal(stats.rakebackClaimed.toNumber(), RAKEBACK);
+    assert.equal(await unclaimedRakeback(), 0);
+
+    await expectError(claimRakeback(), "NothingToClaim");
+  });
+
+  it("credits no rakeback on a refunded game", async () => {
+    const { nonce } = await plinko.play(player, { bet: BET });
+    await plinko.warp(60 * 60);
+    await plinko.refund(player, nonce);
+
+    assert.equal((await userStats()).rakebackAccrued.toNumber(), 0);
+    assert.equal(await unclaimedRakeback(), 0);
+  });
+
+  it("stops counting refunded and cancelled bets towards the tiers", async () => {
+    const wageredBefore = (await userStats()).totalWagered.toNumber();
+
+    const refunded = await plinko.play(player, { bet: BET });
+    assert.equal((await userStats()).totalWagered.toNumber(), wageredBefore + BET);
+    await plinko.warp(60 * 60);
+    await plinko.refund(player, refunded.nonce);
+    assert.equal((await userStats()).totalWagered.toNumber(), wageredBefore);
+
+    const cancelled = await plinko.play(player, { bet: BET });
+    await plinko.cancel(player.publicKey, cancelled.nonce);
+    assert.equal((await userStats()).totalWagered.toNumber(), wageredBefore);
+  });
+
+  it("discounts the winnings fee at settlement", async () => {
+    await plinko.program.methods
+      .setFeeModel({ winnings: {} } as any, new BN(500))
+      .accountsStrict({
+        plinkoStatus: plinko.plinkoStatus,
+        roles: null,
+        authority: plinko.authority.publicKey,
+      })
+      .signers([plinko.authority])
+      .rpc();
+
+    const { nonce } = await plinko.play(player, { bet: BET });
+    const treasuryBefore = await plinko.balance(plinko.feeTreasury.publicKey);
+    await plinko.fulfill(player.publicKey, nonce);
+
+    // Half of 5% of the 1 SOL won
+    assert.equal(
+      await plinko.balance(plinko.feeTreasury.publicKey),
+      treasuryBefore + 25_000_000
+    );
+  });
+});
@@ -0,0 +1,764 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SolanaPlinkoSmartContract } from "../target/types/solana_plinko_smart_contract";
+import IDL from "../target/idl/solana_plinko_smart_contract.json";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  LAMPORTS_PER_SOL,
+} from "@solana/web3.js";
+import { BN } from "bn.js";
+import { assert } from "chai";
+import { createHash } from "crypto";
+import { startAnchor, ProgramTestContext, Clock } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+
+// Shared bankrun fixture for the behaviour tests. The Orao VRF program is loaded from
+// tests/fixtures/orao_vrf.so (`yarn fixtures` dumps it from mainnet), its network state is
+// written by hand and randomness is fulfilled by rewriting the request account
+
+export const VRF_PROGRAM_ID = new PublicKey(
+  "VRFzZoJdhFWL8rkvu87LpKM3RbcVezpMEc6X5GVDr7y"
+);
+export const TOKEN_PROGRAM_ID = anchor.utils.token.TOKEN_PROGRAM_ID;
+export const ASSOCIATED_TOKEN_PROGRAM_ID = anchor.utils.token.ASSOCIATED_PROGRAM_ID;
+
+export const RISK = {
+  low: { low: {} },
+  medium: { medium: {} },
+  high: { high: {} },
+};
+const RISK_INDEX = { low: 0, medium: 1, high: 2 };
+
+const MINT_SPACE = 82;
+const TOKEN_ACCOUNT_SPACE = 165;
+const TOKEN_AMOUNT_OFFSET = 64;
+const MINT_SUPPLY_OFFSET = 36;
+
+export const accountDiscriminator = (name: string) =>
+  createHash("sha256").update(`account:${name}`).digest().subarray(0, 8);
+
+const u64 = (value: number | BN) =>
+  new BN(value).toArrayLike(Buffer, "le", 8);
+
+export type Risk = keyof typeof RISK;
+
+export interface PlayOptions {
+  balls?: number;
+  bet?: number;
+  risk?: Risk;
+  rows?: number;
+  integrator?: PublicKey;
+  integratorFee?: number;
+  // `undefined` passes the account when it exists, `null` leaves it out
+  referral?: PublicKey | null;
+  jackpot?: PublicKey | null;
+  feeTiers?: PublicKey | null;
+}
+
+export interface SettleOptions {
+  referral?: PublicKey | null;
+  jackpot?: PublicKey | null;
+}
+
+export interface TokenGame {
+  mint: PublicKey;
+  playerTokenAccount: PublicKey;
+  feeTreasuryTokenAccount: PublicKey;
+}
+
+export class Plinko {
+  context: ProgramTestContext;
+  program: Program<SolanaPlinkoSmartContract>;
+
+  authority = Keypair.generate();
+  feeTreasury = Keypair.generate();
+  vrfTreasury = Keypair.generate();
+
+  plinkoStatus: PublicKey;
+  house: PublicKey;
+  vault: PublicKey;
+  roles: PublicKey;
+  jackpot: PublicKey;
+  feeTiers: PublicKey;
+  feeDistribution: PublicKey;
+  feeVault: PublicKey;
+  multisig: PublicKey;
+  vrfConfig: PublicKey;
+
+  static async start(): Promise<Plinko> {
+    const plinko = new Plinko();
+    plinko.context = await startAnchor(
+      ".",
+      [{ name: "orao_vrf", programId: VRF_PROGRAM_ID }],
+      []
+    );
+    plinko.program = new Program<SolanaPlinkoSmartContract>(
+      IDL as SolanaPlinkoSmartContract,
+      new BankrunProvider(plinko.context)
+    );
+
+    plinko.plinkoStatus = plinko.pda("plinko_status");
+    plinko.house = plinko.pda("house");
+    plinko.vault = plinko.pda("vaultseed");
+    plinko.roles = plinko.pda("roles");
+    plinko.jackpot = plinko.pda("jackpot");
+    plinko.feeTiers = plinko.pda("fee_tiers");
+    plinko.feeDistribution = plinko.pda("fee_distribution");
+    plinko.feeVault = plinko.pda("fee_vault");
+    plinko.multisig = plinko.pda("multisig");
+    [plinko.vrfConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("orao-vrf-network-configuration")],
+      VRF_PROGRAM_ID
+    );
+
+    plinko.fund(plinko.authority.publicKey, 100 * LAMPORTS_PER_SOL);
+    plinko.fund(plinko.feeTreasury.publicKey, LAMPORTS_PER_SOL);
+    plinko.fund(plinko.vrfTreasury.publicKey, LAMPORTS_PER_SOL);
+    plinko.setNetworkState();
+    return plinko;
+  }
+
+  pda(...seeds: (string | Buffer | Uint8Array)[]): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      seeds.map((seed) => (typeof seed === "string" ? Buffer.from(seed) : seed)),
+      this.program.programId
+    )[0];
+  }
+
+  gamePda(player: PublicKey, nonce: number) {
+    return this.pda("game", player.toBuffer(), u64(nonce));
+  }
+
+  escrowPda(game: PublicKey) {
+    return this.pda("escrow", game.toBuffer());
+  }
+
+  userStatsPda(player: PublicKey) {
+    return this.pda("user_stats", player.toBuffer());
+  }
+
+  historyPagePda(player: PublicKey, nonce: number) {
+    // Pages hold 50 games each
+    return this.pda("history", player.toBuffer(), u64(Math.floor(nonce / 50)));
+  }
+
+  payoutTablePda(risk: Risk, rows: number) {
+    return this.pda(
+      "payout_table",
+      Buffer.from([RISK_INDEX[risk]]),
+      Buffer.from([rows])
+    );
+  }
+
+  referralPda(code: string) {
+    return this.pda("referral", referralCode(code));
+  }
+
+  integratorStatsPda(integrator: PublicKey) {
+    return this.pda("integrator", integrator.toBuffer());
+  }
+
+  randomPda(force: number[]) {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("orao-vrf-randomness-request"), Buffer.from(force)],
+      VRF_PROGRAM_ID
+    )[0];
+  }
+
+  fund(address: PublicKey, lamports: number) {
+    this.context.setAccount(address, {
+      lamports,
+      data: Buffer.alloc(0),
+      owner: SystemProgram.programId,
+      executable: false,
+    });
+  }
+
+  wallet(lamports = 100 * LAMPORTS_PER_SOL) {
+    const wallet = Keypair.generate();
+    this.fund(wallet.publicKey, lamports);
+    return wallet;
+  }
+
+  async balance(address: PublicKey): Promise<number> {
+    return Number(await this.context.banksClient.getBalance(address));
+  }
+
+  async exists(address: PublicKey): Promise<boolean> {
+    return (await this.context.banksClient.getAccount(address)) !== null;
+  }
+
+  async rentExempt(space: number): Promise<number> {
+    const rent = await this.context.banksClient.getRent();
+    return Number(rent.minimumBalance(BigInt(space)));
+  }
+
+  // Moves to the next slot, which also gives retried transactions a new blockhash
+  async warp(seconds: number) {
+    const clock = await this.context.banksClient.getClock();
+    const slot = clock.slot + BigInt(1);
+    this.context.warpToSlot(slot);
+    this.context.setClock(
+      new Clock(
+        slot,
+        clock.epochStartTimestamp,
+        clock.epoch,
+        clock.leaderScheduleEpoch,
+        clock.unixTimestamp + BigInt(seconds)
+      )
+    );
+  }
+
+  // Passes optional accounts the way the frontend does: when they exist
+  private async optional(
+    override: PublicKey | null | undefined,
+    address: PublicKey
+  ): Promise<PublicKey | null> {
+    if (override !== undefined) {
+      return override;
+    }
+    return (await this.exists(address)) ? address : null;
+  }
+
+  private async boundReferral(player: PublicKey): Promise<PublicKey | null> {
+    const userStats = this.userStatsPda(player);
+    if (!(await this.exists(userStats))) {
+      return null;
+    }
+    const { referrer } = await this.program.account.userStats.fetch(userStats);
+    return referrer.equals(PublicKey.default) ? null : referrer;
+  }
+
+  async nextNonce(player: PublicKey): Promise<number> {
+    const userStats = this.userStatsPda(player);
+    if (!(await this.exists(userStats))) {
+      return 0;
+    }
+    return (await this.program.account.userStats.fetch(userStats)).nextNonce.toNumber();
+  }
+
+  async feeTreasuryKey(): Promise<PublicKey> {
+    return (await this.program.account.plinkoStatus.fetch(this.plinkoStatus))
+      .feeTreasury;
+  }
+
+  // Orao network configuration, requests cost `requestFee` paid to `vrfTreasury`
+  setNetworkState(requestFee = 0) {
+    const data = Buffer.alloc(8 + 464);
+    let offset = 0;
+    const write = (bytes: Buffer) => {
+      bytes.copy(data, offset);
+      offset += bytes.length;
+    };
+    write(accountDiscriminator("NetworkState"));
+    write(this.authority.publicKey.toBuffer()); // authority
+    write(this.vrfTreasury.publicKey.toBuffer()); // treasury
+    write(u64(requestFee)); // request_fee
+    write(Buffer.from([1, 0, 0, 0])); // fulfillment_authorities
+    write(Keypair.generate().publicKey.toBuffer());
+    write(Buffer.from([0])); // token_fee_config
+    write(u64(0)); // num_received
+    this.context.setAccount(this.vrfConfig, {
+      lamports: LAMPORTS_PER_SOL,
+      data,
+      owner: VRF_PROGRAM_ID,
+      executable: false,
+    });
+  }
+
+  // Replaces the pending request with fulfilled randomness, the first 8 bytes are the game's roll
+  fulfillRandomness(force: number[], randomness: number | BN = 1) {
+    const data = Buffer.alloc(8 + 32 + 64 + 4);
+    accountDiscriminator("Randomness").copy(data, 0);
+    Buffer.from(force).copy(data, 8);
+    u64(randomness).copy(data, 40);
+    data.fill(7, 48, 104);
+    this.context.setAccount(this.randomPda(force), {
+      lamports: LAMPORTS_PER_SOL,
+      data,
+      owner: VRF_PROGRAM_ID,
+      executable: false,
+    });
+  }
+
+  async initialize(platformFee = 300, minBuyIn = 1_000_000, maxBalls = 10) {
+    await this.program.methods
+      .initialize(new BN(platformFee), new BN(minBuyIn), maxBalls)
+      .accountsStrict({
+        plinkoStatus: this.plinkoStatus,
+        house: this.house,
+        authority: this.authority.publicKey,
+        feeTreasury: this.feeTreasury.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([this.authority])
+      .rpc();
+  }
+
+  // Board with the same multiplier in every bucket, so payouts don't depend on the roll
+  async setPayout(
+    multiplier: number,
+    risk: Risk = "medium",
+    rows = 8,
+    signer = this.authority
+  ) {
+    await this.program.methods
+      .setPayout(RISK[risk], rows, [], Array(rows + 1).fill(new BN(multiplier)))
+      .accountsStrict({
+        plinkoStatus: this.plinkoStatus,
+        payoutTable: this.payoutTablePda(risk, rows),
+        roles: await this.optional(undefined, this.roles),
+        authority: signer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  async setRefundTimeout(refundTimeout: number, refundPlatformFee: boolean) {
+    await this.program.methods
+      .setRefundTimeout(new BN(refundTimeout), refundPlatformFee)
+      .accountsStrict({
+        plinkoStatus: this.plinkoStatus,
+        roles: null,
+        authority: this.authority.publicKey,
+      })
+      .signers([this.authority])
+      .rpc();
+  }
+
+  // Default setup: 3% fee, a 2x board and a funded vault
+  async setup(vaultLamports = 100 * LAMPORTS_PER_SOL) {
+    await this.initialize();
+    await this.setPayout(200);
+    this.fund(this.vault, vaultLamports);
+  }
+
+  async play(player: Keypair, options: PlayOptions = {}) {
+    const {
+      balls = 1,
+      bet = LAMPORTS_PER_SOL,
+      risk = "medium",
+      rows = 8,
+      integrator,
+      integratorFee = 0,
+    } = options;
+    const nonce = await this.nextNonce(player.publicKey);
+    const game = this.gamePda(player.publicKey, nonce);
+    const force = Array.from(Keypair.generate().publicKey.toBytes());
+    const referral =
+      options.referral === undefined
+        ? await this.boundReferral(player.publicKey)
+        : options.referral;
+
+    await this.program.methods
+      .playGame(
+        force,
+        balls,
+        RISK[risk],
+        rows,
+        new BN(bet),
+        new BN(integratorFee)
+      )
+      .accountsStrict({
+        plinkoStatus: this.plinkoStatus,
+        payoutTable: this.payoutTablePda(risk, rows),
+        userStats: this.userStatsPda(player.publicKey),
+        historyPage: this.historyPagePda(player.publicKey, nonce),
+        game,
+        escrow: this.escrowPda(game),
+        house: this.house,
+        player: player.publicKey,
+        vault: this.vault,
+        treasury: this.vrfTreasury.publicKey,
+        random: this.randomPda(force),
+        config: this.vrfConfig,
+        vrf: VRF_PROGRAM_ID,
+        feeTreasury: await this.feeTreasuryKey(),
+        referral,
+        integrator: integrator ?? null,
+        integratorStats: integrator ? this.integratorStatsPda(integrator) : null,
+        feeTiers: await this.optional(options.feeTiers, this.feeTiers),
+        jackpot: await this.optional(options.jackpot, this.jackpot),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([player])
+      .rpc();
+
+    return { game, nonce, force, escrow: this.escrowPda(game) };
+  }
+
+  async fulfill(
+    player: PublicKey,
+    nonce: number,
+    randomness: number | BN = 1,
+    options: SettleOptions = {}
+  ) {
+    const game = this.gamePda(player, nonce);
+    const { force, requestId } = await this.program.account.game.fetch(game);
+    this.fulfillRandomness(force, randomness);
+
+    await this.program.methods
+      .fulfillRandomWords(force, new BN(nonce), requestId)
+      .accountsStrict({
+        plinkoStatus: this.plinkoStatus,
+        game,
+        payoutTable: await this.gamePayoutTable(game),
+        house: this.house,
+        userStats: this.userStatsPda(player),
+        historyPage: this.historyPagePda(player, nonce),
+        escrow: this.escrowPda(game),
+        vault: this.vault,
+        random: this.randomPda(force),
+        player,
+        feeTreasury: await this.feeTreasuryKey(),
+        referral:
+          options.referral === undefined
+            ? await this.boundReferral(player)
+            : options.referral,
+        jackpot: await this.optional(options.jackpot, this.jackpot),
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  }
+
+  async refund(player: Keypair, nonce: number, options: SettleOptions = {}) {
+    const game = this.gamePda(player.publicKey, nonce);
+    const { force } = await this.program.account.game.fetch(game);
+
+    await this.program.methods
+      .refundExpiredGame(new BN(nonce))
+      .accountsStrict({
+        plinkoStatus: this.plinkoStatus,
+        game,
+        userStats: this.userStatsPda(player.publicKey),
+        historyPage: this.historyPagePda(player.publicKey, nonce),
+        escrow: this.escrowPda(game),
+        house: this.house,
+        vault: this.vault,
+        random: this.randomPda(force),
+        referral:
+          options.referral === undefined
+            ? await this.boundReferral(player.publicKey)
+            : options.referral,
+        jackpot: await this.optional(options.jackpot, this.jackpot),
+        player: player.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([player])
+      .rpc();
+  }
+
+  async cancel(
+    player: PublicKey,
+    nonce: number,
+    signer = this.authority,
+    options: SettleOptions = {}
+  ) {
+    const game = this.gamePda(player, nonce);
+    const { force } = await this.program.account.game.fetch(game);
+
+    await this.program.methods
+      .cancelGame(new BN(nonce))
+      .accountsStrict({
+        plinkoStatus: this.plinkoStatus,
+        game,
+        userStats: this.userStatsPda(player),
+        historyPage: this.historyPagePda(player, nonce),
+        escrow: this.escrowPda(game),
+        house: this.house,
+        vault: this.vault,
+        random: this.randomPda(force),
+        player,
+        referral:
+          options.referral === undefined
+            ? await this.boundReferral(player)
+            : options.referral,
+        jackpot: await this.optional(options.jackpot, this.jackpot),
+        roles: await this.optional(undefined, this.roles),
+        authority: signer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  private async gamePayoutTable(game: PublicKey) {
+    const { riskLevel, rows } = await this.program.account.game.fetch(game);
+    return this.payoutTablePda(Object.keys(riskLevel)[0] as Risk, rows);
+  }
+
+  changePda(id: number) {
+    return this.pda("change", u64(id));
+  }
+
+  // Queues a parameter change and returns its id
+  async proposeChange(change: object, signer = this.authority): Promise<number> {
+    const { nextChangeId } = await this.program.account.plinkoStatus.fetch(
+      this.plinkoStatus
+    );
+    const id = nextChangeId.toNumber();
+
+    await this.program.methods
+      .proposeChange(change as any)
+      .accountsStrict({
+        plinkoStatus: this.plinkoStatus,
+        pendingChange: this.changePda(id),
+        roles: await this.optional(undefined, this.roles),
+        authority: signer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc();
+    return id;
+  }
+
+  // Only the account the change writes has to be passed
+  async executeChange(
+    id: number,
+    accounts: {
+      payoutTable?: PublicKey;
+      feeDistribution?: boolean;
+      feeTiers?: boolean;
+      mintConfig?: PublicKey;
+    } = {},
+    executor = this.authority
+  ) {
+    const { proposer } = await this.program.account.pendingChange.fetch(
+      this.changePda(id)
+    );
+
+    await this.program.methods
+      .executeChange(new BN(id))
+      .accountsStrict({
+        plinkoStatus: this.plinkoStatus,
+        pendingChange: this.changePda(id),
+        payoutTable: accounts.payoutTable ?? null,
+        feeDistribution: accounts.feeDistribution ? this.feeDistribution : null,
+        feeVault: accounts.feeDistribution ? this.feeVault : null,
+        feeTiers: accounts.feeTiers ? this.feeTiers : null,
+        mintConfig: accounts.mintConfig ?? null,
+        proposer,
+        executor: executor.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([executor])
+      .rpc();
+  }
+
+  multisigTransactionPda(id: number) {
+    return this.pda("multisig_tx", u64(id));
+  }
+
+  // Proposes a multisig transaction, which counts as the proposer's approval
+  async proposeMultisig(action: object, proposer: Keypair): Promise<number> {
+    const { nextTransactionId } = await this.program.account.multisig.fetch(
+      this.multisig
+    );
+    const id = nextTransactionId.toNumber();
+
+    await this.program.methods
+      .proposeMultisigTransaction(action as any)
+      .accountsStrict({
+        multisig: this.multisig,
+        transaction: this.multisigTransactionPda(id),
+        proposer: proposer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([proposer])
+      .rpc();
+    return id;
+  }
+
+  async approveMultisig(id: number, signer: Keypair) {
+    await this.program.methods
+      .approveMultisigTransaction(new BN(id))
+      .accountsStrict({
+        multisig: this.multisig,
+        transaction: this.multisigTransactionPda(id),
+        signer: signer.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  // SPL Token accounts are written directly, the tests only need balances to move
+
+  createMint(decimals = 6): PublicKey {
+    const mint = Keypair.generate().publicKey;
+    const data = Buffer.alloc(MINT_SPACE);
+    data.writeUInt32LE(1, 0); // mint_authority
+    this.authority.publicKey.toBuffer().copy(data, 4);
+    data.writeUInt8(decimals, 44);
+    data.writeUInt8(1, 45); // is_initialized
+    this.context.setAccount(mint, {
+      lamports: LAMPORTS_PER_SOL,
+      data,
+      owner: TOKEN_PROGRAM_ID,
+      executable: false,
+    });
+    return mint;
+  }
+
+  createTokenAccount(mint: PublicKey, owner: PublicKey, amount = 0): PublicKey {
+    const address = Keypair.generate().publicKey;
+    const data = Buffer.alloc(TOKEN_ACCOUNT_SPACE);
+    mint.toBuffer().copy(data, 0);
+    owner.toBuffer().copy(data, 32);
+    u64(amount).copy(data, TOKEN_AMOUNT_OFFSET);
+    data.writeUInt8(1, 108); // state = Initialized
+    this.context.setAccount(address, {
+      lamports: LAMPORTS_PER_SOL,
+      data,
+      owner: TOKEN_PROGRAM_ID,
+      executable: false,
+    });
+    return address;
+  }
+
+  async setTokenBalance(address: PublicKey, amount: number) {
+    const account = await this.context.banksClient.getAccount(address);
+    const data = Buffer.from(account.data);
+    u64(amount).copy(data, TOKEN_AMOUNT_OFFSET);
+    this.context.setAccount(address, { ...account, data });
+  }
+
+  async tokenBalance(address: PublicKey): Promise<number> {
+    const account = await this.context.banksClient.getAccount(address);
+    return new BN(
+      Buffer.from(account.data).subarray(
+        TOKEN_AMOUNT_OFFSET,
+        TOKEN_AMOUNT_OFFSET + 8
+      ),
+      "le"
+    ).toNumber();
+  }
+
+  async mintSupply(mint: PublicKey): Promise<number> {
+    const account = await this.context.banksClient.getAccount(mint);
+    return new BN(
+      Buffer.from(account.data).subarray(
+        MINT_SUPPLY_OFFSET,
+        MINT_SUPPLY_OFFSET + 8
+      ),
+      "le"
+    ).toNumber();
+  }
+
+  mintConfigPda(mint: PublicKey) {
+    return this.pda("mint_config", mint.toBuffer());
+  }
+
+  tokenVaultPda(mint: PublicKey) {
+    return this.pda("token_vault", mint.toBuffer());
+  }
+
+  async addMint(mint: PublicKey, platformFee = 300, minBuyIn = 1_000) {
+    await this.program.methods
+      .addMint(new BN(platformFee), new BN(minBuyIn))
+      .accountsStrict({
+        plinkoStatus: this.plinkoStatus,
+        mintConfig: this.mintConfigPda(mint),
+        mint,
+        vault: this.vault,
+        vaultTokenAccount: this.tokenVaultPda(mint),
+        roles: null,
+        authority: this.authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([this.authority])
+      .rpc();
+  }
+
+  async playToken(
+    player: Keypair,
+    token: TokenGame,
+    bet: number,
+    balls = 1,
+    risk: Risk = "medium",
+    rows = 8
+  ) {
+    const nonce = await this.nextNonce(player.publicKey);
+    const game = this.gamePda(player.publicKey, nonce);
+    const force = Array.from(Keypair.generate().publicKey.toBytes());
+
+    await this.program.methods
+      .playGameToken(force, balls, RISK[risk], rows, new BN(bet))
+      .accountsStrict({
+        plinkoStatus: this.plinkoStatus,
+        payoutTable: this.payoutTablePda(risk, rows),
+        mintConfig: this.mintConfigPda(token.mint),
+        mint: token.mint,
+        userStats: this.userStatsPda(player.publicKey),
+        historyPage: this.historyPagePda(player.publicKey, nonce),
+        game,
+        player: player.publicKey,
+        playerTokenAccount: token.playerTokenAccount,
+        vaultTokenAccount: this.tokenVaultPda(token.mint),
+        vault: this.vault,
+        escrowTokenAccount: this.escrowPda(game),
+        feeTreasuryTokenAccount: token.feeTreasuryTokenAccount,
+        treasury: this.vrfTreasury.publicKey,
+        random: this.randomPda(force),
+        config: this.vrfConfig,
+        vrf: VRF_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([player])
+      .rpc();
+
+    return { game, nonce, force, escrow: this.escrowPda(game) };
+  }
+
+  async fulfillToken(
+    player: PublicKey,
+    nonce: number,
+    token: TokenGame,
+    randomness: number | BN = 1
+  ) {
+    const game = this.gamePda(player, nonce);
+    const { force, requestId } = await this.program.account.game.fetch(game);
+    this.fulfillRandomness(force, randomness);
+
+    await this.program.methods
+      .fulfillRandomWordsToken(force, new BN(nonce), requestId)
+      .accountsStrict({
+        plinkoStatus: this.plinkoStatus,
+        game,
+        payoutTable: await this.gamePayoutTable(game),
+        mintConfig: this.mintConfigPda(token.mint),
+        mint: token.mint,
+        userStats: this.userStatsPda(player),
+        historyPage: this.historyPagePda(player, nonce),
+        vault: this.vault,
+        vaultTokenAccount: this.tokenVaultPda(token.mint),
+        escrowTokenAccount: this.escrowPda(game),
+        random: this.randomPda(force),
+        playerTokenAccount: token.playerTokenAccount,
+        feeTreasuryTokenAccount: token.feeTreasuryTokenAccount,
+        player,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+  }
+}
+
+export const referralCode = (code: string) => {
+  const bytes = Buffer.alloc(16);
+  Buffer.from(code).copy(bytes);
+  return bytes;
+};
+
+export const expectError = async (promise: Promise<unknown>, code: string) => {
+  try {
+    await promise;
+  } catch (err) {
+    assert.include(err.message, code);
+    return;
+  }
+  assert.fail(`expected ${code}`);
+};
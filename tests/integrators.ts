@@ -0,0 +1,129 @@
+import { Keypair, LAMPORTS_PER_SOL, SystemProgram } from "@solana/web3.js";
+import { BN } from "bn.js";
+import { assert } from "chai";
+import { Plinko, expectError } from "./helpers";
+
+describe("Integrators", () => {
+  let plinko: Plinko;
+  let integrator: Keypair;
+  let player: Keypair;
+  let escrowRent: number;
+
+  const BET = LAMPORTS_PER_SOL;
+  const FEE = 30_000_000; // 3% of the bet
+  const INTEGRATOR_FEE = 10_000_000; // 1% of the bet
+  const STAKE = BET - FEE - INTEGRATOR_FEE;
+
+  const setMaxIntegratorFee = (fee: number, signer = plinko.authority) =>
+    plinko.program.methods
+      .setMaxIntegratorFee(new BN(fee))
+      .accountsStrict({
+        plinkoStatus: plinko.plinkoStatus,
+        roles: null,
+        authority: signer.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+
+  const integratorStats = () =>
+    plinko.program.account.integratorStats.fetch(
+      plinko.integratorStatsPda(integrator.publicKey)
+    );
+
+  before(async () => {
+    plinko = await Plinko.start();
+    await plinko.setup();
+    integrator = plinko.wallet();
+    player = plinko.wallet();
+    escrowRent = await plinko.rentExempt(0);
+
+    await plinko.program.methods
+      .registerIntegrator()
+      .accountsStrict({
+        integratorStats: plinko.integratorStatsPda(integrator.publicKey),
+        integrator: integrator.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([integrator])
+      .rpc();
+  });
+
+  it("rejects integrator fees until a cap is set", async () => {
+    await expectError(
+      plinko.play(player, { integrator: integrator.publicKey, integratorFee: 100 }),
+      "IntegratorFeeTooHigh"
+    );
+  });
+
+  it("only lets operators set a cap of up to 5%", async () => {
+    await expectError(setMaxIntegratorFee(100, plinko.wallet()), "MissingRole");
+    await expectError(setMaxIntegratorFee(501), "IntegratorFeeTooHigh");
+    await setMaxIntegratorFee(100);
+  });
+
+  it("pays the integrator fee out of the bet", async () => {
+    const integratorBefore = await plinko.balance(integrator.publicKey);
+    const treasuryBefore = await plinko.balance(plinko.feeTreasury.publicKey);
+    const { game, escrow } = await plinko.play(player, {
+      bet: BET,
+      integrator: integrator.publicKey,
+      integratorFee: 100,
+    });
+
+    assert.equal(
+      await plinko.balance(integrator.publicKey),
+      integratorBefore + INTEGRATOR_FEE
+    );
+    assert.equal(
+      await plinko.balance(plinko.feeTreasury.publicKey),
+      treasuryBefore + FEE
+    );
+    assert.equal(await plinko.balance(escrow), STAKE + escrowRent);
+
+    const gameAccount = await plinko.program.account.game.fetch(game);
+    assert.equal(gameAccount.amountForHouse.toNumber(), STAKE);
+
+    const stats = await integratorStats();
+    assert.equal(stats.totalGames.toNumber(), 1);
+    assert.equal(stats.totalVolume.toNumber(), BET);
+    assert.equal(stats.totalFees.toNumber(), INTEGRATOR_FEE);
+  });
+
+  it("pays winnings on the stake left after both fees", async () => {
+    const before = await plinko.balance(player.publicKey);
+    await plinko.fulfill(player.publicKey, 0);
+
+    assert.equal(
+      await plinko.balance(player.publicKey),
+      before + 2 * STAKE + escrowRent
+    );
+  });
+
+  it("rejects fees above the cap", async () => {
+    await expectError(
+      plinko.play(player, { integrator: integrator.publicKey, integratorFee: 101 }),
+      "IntegratorFeeTooHigh"
+    );
+  });
+
+  it("keeps the integrator fee out of refunds", async () => {
+    await plinko.setRefundTimeout(60 * 60, true);
+    const { nonce } = await plinko.play(player, {
+      bet: BET,
+      integrator: integrator.publicKey,
+      integratorFee: 100,
+    });
+    await plinko.warp(60 * 60);
+
+    const playerBefore = await plinko.balance(player.publicKey);
+    const integratorBefore = await plinko.balance(integrator.publicKey);
+    await plinko.refund(player, nonce);
+
+    // The platform fee comes back, the integrator fee already left the program
+    assert.equal(
+      await plinko.balance(player.publicKey),
+      playerBefore + BET - INTEGRATOR_FEE + escrowRent
+    );
+    assert.equal(await plinko.balance(integrator.publicKey), integratorBefore);
+  });
+});
@@ -0,0 +1,124 @@
+import { Keypair, LAMPORTS_PER_SOL, SystemProgram } from "@solana/web3.js";
+import { BN } from "bn.js";
+import { assert } from "chai";
+import { Plinko, expectError } from "./helpers";
+
+describe("Jackpot", () => {
+  let plinko: Plinko;
+  let player: Keypair;
+  let escrowRent: number;
+
+  const BET = LAMPORTS_PER_SOL;
+  const FEE = 30_000_000; // 3% of the bet
+  const CONTRIBUTION = 10_000_000; // 1% of the bet
+  const STAKE = BET - FEE - CONTRIBUTION;
+
+  const createJackpot = (signer = plinko.authority) =>
+    plinko.program.methods
+      .createJackpot()
+      .accountsStrict({
+        plinkoStatus: plinko.plinkoStatus,
+        jackpot: plinko.jackpot,
+        roles: null,
+        authority: signer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc();
+
+  const setJackpot = (jackpotFee: number, jackpotOdds: number) =>
+    plinko.program.methods
+      .setJackpot(new BN(jackpotFee), new BN(jackpotOdds))
+      .accountsStrict({
+        plinkoStatus: plinko.plinkoStatus,
+        jackpot: plinko.jackpot,
+        roles: null,
+        authority: plinko.authority.publicKey,
+      })
+      .signers([plinko.authority])
+      .rpc();
+
+  const fetchJackpot = () => plinko.program.account.jackpot.fetch(plinko.jackpot);
+
+  before(async () => {
+    plinko = await Plinko.start();
+    await plinko.setup();
+    player = plinko.wallet();
+    escrowRent = await plinko.rentExempt(0);
+  });
+
+  it("only lets operators open the pool", async () => {
+    await expectError(createJackpot(plinko.wallet()), "MissingRole");
+    await createJackpot();
+
+    await expectError(setJackpot(501, 1), "InvalidJackpotConfig");
+    await expectError(setJackpot(100, 0), "InvalidJackpotConfig");
+
+    // Every settled bet hits with odds of one
+    await setJackpot(100, 1);
+  });
+
+  it("requires the pool on every bet", async () => {
+    await expectError(plinko.play(player, { jackpot: null }), "MissingJackpot");
+  });
+
+  it("feeds part of every bet into the pool", async () => {
+    const poolBefore = await plinko.balance(plinko.jackpot);
+    const { game, escrow } = await plinko.play(player, { bet: BET });
+
+    assert.equal(await plinko.balance(plinko.jackpot), poolBefore + CONTRIBUTION);
+    assert.equal(await plinko.balance(escrow), STAKE + escrowRent);
+
+    const gameAccount = await plinko.program.account.game.fetch(game);
+    assert.equal(gameAccount.jackpotContribution.toNumber(), CONTRIBUTION);
+
+    const jackpot = await fetchJackpot();
+    assert.equal(jackpot.balance.toNumber(), CONTRIBUTION);
+    assert.equal(jackpot.totalContributed.toNumber(), CONTRIBUTION);
+  });
+
+  it("returns the contribution of a refunded game", async () => {
+    const { nonce } = await plinko.play(player, { bet: BET });
+    assert.equal((await fetchJackpot()).balance.toNumber(), 2 * CONTRIBUTION);
+    await plinko.warp(60 * 60);
+
+    const playerBefore = await plinko.balance(player.publicKey);
+    const poolBefore = await plinko.balance(plinko.jackpot);
+    await plinko.refund(player, nonce);
+
+    assert.equal(
+      await plinko.balance(player.publicKey),
+      playerBefore + STAKE + CONTRIBUTION + escrowRent
+    );
+    assert.equal(await plinko.balance(plinko.jackpot), poolBefore - CONTRIBUTION);
+
+    const jackpot = await fetchJackpot();
+    assert.equal(jackpot.balance.toNumber(), CONTRIBUTION);
+    assert.equal(jackpot.totalContributed.toNumber(), CONTRIBUTION);
+  });
+
+  it("pays the whole pool on a hit", async () => {
+    const playerBefore = await plinko.balance(player.publicKey);
+    const poolBefore = await plinko.balance(plinko.jackpot);
+    await plinko.fulfill(player.publicKey, 0);
+
+    // The 2x payout plus the pool
+    assert.equal(
+      await plinko.balance(player.publicKey),
+      playerBefore + 2 * STAKE + escrowRent + CONTRIBUTION
+    );
+    assert.equal(await plinko.balance(plinko.jackpot), poolBefore - CONTRIBUTION);
+
+    const game = await plinko.program.account.game.fetch(
+      plinko.gamePda(player.publicKey, 0)
+    );
+    assert.equal(game.jackpotPayout.toNumber(), CONTRIBUTION);
+
+    const jackpot = await fetchJackpot();
+    assert.equal(jackpot.balance.toNumber(), 0);
+    assert.equal(jackpot.totalPaid.toNumber(), CONTRIBUTION);
+    assert.equal(jackpot.totalHits.toNumber(), 1);
+    assert.ok(jackpot.recentHits[0].player.equals(player.publicKey));
+    assert.equal(jackpot.recentHits[0].amount.toNumber(), CONTRIBUTION);
+  });
+});
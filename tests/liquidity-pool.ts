@@ -0,0 +1,204 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram } from "@solana/web3.js";
+import { BN } from "bn.js";
+import { assert } from "chai";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  Plinko,
+  TOKEN_PROGRAM_ID,
+  expectError,
+} from "./helpers";
+
+describe("Liquidity pool", () => {
+  let plinko: Plinko;
+  let lpMint: PublicKey;
+  let seedOwner: Keypair;
+  let depositor: Keypair;
+  let player: Keypair;
+  let seedShares: number;
+
+  const DEPOSIT = 10 * LAMPORTS_PER_SOL;
+  const BET = LAMPORTS_PER_SOL;
+  const STAKE = BET - 30_000_000; // after the 3% fee
+
+  const shareAccount = (owner: PublicKey) =>
+    anchor.utils.token.associatedAddress({ mint: lpMint, owner });
+
+  const enable = (signer = plinko.authority) =>
+    plinko.program.methods
+      .enableLiquidityPool()
+      .accountsStrict({
+        plinkoStatus: plinko.plinkoStatus,
+        house: plinko.house,
+        vault: plinko.vault,
+        lpMint,
+        seedShareOwner: seedOwner.publicKey,
+        seedShareAccount: shareAccount(seedOwner.publicKey),
+        multisig: null,
+        transaction: null,
+        authority: signer.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc();
+
+  const deposit = (amount: number) =>
+    plinko.program.methods
+      .depositLiquidity(new BN(amount))
+      .accountsStrict({
+        house: plinko.house,
+        vault: plinko.vault,
+        lpMint,
+        depositorShareAccount: shareAccount(depositor.publicKey),
+        depositor: depositor.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([depositor])
+      .rpc();
+
+  const redeem = (redeemer: Keypair, shares: number) =>
+    plinko.program.methods
+      .redeemLiquidity(new BN(shares))
+      .accountsStrict({
+        house: plinko.house,
+        vault: plinko.vault,
+        lpMint,
+        redeemerShareAccount: shareAccount(redeemer.publicKey),
+        redeemer: redeemer.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([redeemer])
+      .rpc();
+
+  // Lamports backing the shares: the vault above its rent-exempt minimum
+  const netAssetValue = async () =>
+    (await plinko.balance(plinko.vault)) - (await plinko.rentExempt(0));
+
+  before(async () => {
+    plinko = await Plinko.start();
+    await plinko.setup();
+    await plinko.setPayout(50, "low");
+    lpMint = plinko.pda("lp_mint");
+    seedOwner = plinko.wallet();
+    depositor = plinko.wallet();
+    player = plinko.wallet();
+  });
+
+  it("rejects deposits while the pool is closed", async () => {
+    await expectError(deposit(DEPOSIT), "LiquidityPoolNotEnabled");
+  });
+
+  it("gives the existing bankroll to the seed share owner", async () => {
+    await expectError(enable(plinko.wallet()), "OnlyOwner");
+    await enable();
+
+    // One share per lamport of the bankroll
+    seedShares = await netAssetValue();
+    assert.equal(await plinko.tokenBalance(shareAccount(seedOwner.publicKey)), seedShares);
+    assert.equal(await plinko.mintSupply(lpMint), seedShares);
+
+    const house = await plinko.program.account.house.fetch(plinko.house);
+    assert.isTrue(house.lpEnabled);
+  });
+
+  it("mints shares at the current price", async () => {
+    const vaultBefore = await plinko.balance(plinko.vault);
+    await deposit(DEPOSIT);
+
+    // The price is still one lamport per share
+    assert.equal(await plinko.tokenBalance(shareAccount(depositor.publicKey)), DEPOSIT);
+    assert.equal(await plinko.mintSupply(lpMint), seedShares + DEPOSIT);
+    assert.equal(await plinko.balance(plinko.vault), vaultBefore + DEPOSIT);
+  });
+
+  it("pays out house profits to the shares", async () => {
+    const { nonce } = await plinko.play(player, { bet: BET, risk: "low" });
+    await plinko.fulfill(player.publicKey, nonce);
+
+    // The 0.5x game left half the stake in the vault
+    const supply = seedShares + DEPOSIT;
+    const nav = await netAssetValue();
+    assert.equal(nav, supply + STAKE / 2);
+
+    const expected = Number((BigInt(DEPOSIT) * BigInt(nav)) / BigInt(supply));
+    const before = await plinko.balance(depositor.publicKey);
+    await redeem(depositor, DEPOSIT);
+
+    assert.equal(await plinko.balance(depositor.publicKey), before + expected);
+    assert.isAbove(expected, DEPOSIT);
+    assert.equal(await plinko.tokenBalance(shareAccount(depositor.publicKey)), 0);
+    assert.equal(await plinko.mintSupply(lpMint), seedShares);
+  });
+
+  it("waits for pending games before pricing shares", async () => {
+    await plinko.warp(1); // New slot, so the deposit is not a duplicate of the first one
+    const { nonce } = await plinko.play(player, { bet: BET, risk: "low" });
+    await expectError(deposit(DEPOSIT), "GamesPending");
+    await expectError(redeem(seedOwner, seedShares), "GamesPending");
+
+    await plinko.fulfill(player.publicKey, nonce);
+    await plinko.warp(1);
+    await deposit(DEPOSIT);
+  });
+
+  it("charges cancelled platform fees to the fee side, not the LPs", async () => {
+    const { nonce } = await plinko.play(player, { bet: BET });
+    const vaultBefore = await plinko.balance(plinko.vault);
+    await plinko.cancel(player.publicKey, nonce);
+
+    // The vault advances the fee and counts it as owed, so the share price does not drop
+    const fee = BET - STAKE;
+    assert.equal(await plinko.balance(plinko.vault), vaultBefore - fee);
+    let house = await plinko.program.account.house.fetch(plinko.house);
+    assert.equal(house.feeRefundsOwed.toNumber(), fee);
+
+    const repay = () =>
+      plinko.program.methods
+        .repayFeeRefunds()
+        .accountsStrict({
+          house: plinko.house,
+          vault: plinko.vault,
+          payer: plinko.authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([plinko.authority])
+        .rpc();
+    await repay();
+
+    assert.equal(await plinko.balance(plinko.vault), vaultBefore);
+    house = await plinko.program.account.house.fetch(plinko.house);
+    assert.equal(house.feeRefundsOwed.toNumber(), 0);
+    await plinko.warp(1); // New slot, so the repayment is not a duplicate
+    await expectError(repay(), "NothingToRepay");
+  });
+
+  it("lets LPs redeem while withdrawals are paused", async () => {
+    await plinko.program.methods
+      .setWithdrawalsPause(true)
+      .accountsStrict({
+        plinkoStatus: plinko.plinkoStatus,
+        house: plinko.house,
+        roles: null,
+        authority: plinko.authority.publicKey,
+      })
+      .signers([plinko.authority])
+      .rpc();
+
+    const shares = await plinko.tokenBalance(shareAccount(depositor.publicKey));
+    const half = Math.floor(shares / 2);
+    await redeem(depositor, half);
+    assert.equal(
+      await plinko.tokenBalance(shareAccount(depositor.publicKey)),
+      shares - half
+    );
+  });
+
+  it("rejects a second pool", async () => {
+    await expectError(enable(), "LiquidityPoolActive");
+  });
+});
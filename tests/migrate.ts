@@ -0,0 +1,417 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SolanaPlinkoSmartContract } from "../target/types/solana_plinko_smart_contract";
+import IDL from "../target/idl/solana_plinko_smart_contract.json";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  LAMPORTS_PER_SOL,
+} from "@solana/web3.js";
+import { BN } from "bn.js";
+import { assert } from "chai";
+import { startAnchor, ProgramTestContext } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+
+// Sizes of the layouts deployed before versioning, `8 + LEN` of the version 1 layouts
+const PLINKO_STATUS_V1_SPACE = 972;
+const HOUSE_V1_SPACE = 77;
+const GAME_V1_SPACE = 178;
+const USER_STATS_V1_SPACE = 876;
+
+// Writes an account in an old layout by hand, the program can no longer create these
+class LegacyWriter {
+  buf: Buffer;
+  offset = 0;
+
+  constructor(space: number, discriminator: number[]) {
+    this.buf = Buffer.alloc(space);
+    this.bytes(discriminator);
+  }
+
+  bytes(bytes: number[] | Buffer) {
+    Buffer.from(bytes).copy(this.buf, this.offset);
+    this.offset += bytes.length;
+    return this;
+  }
+
+  u8(value: number) {
+    this.buf.writeUInt8(value, this.offset);
+    this.offset += 1;
+    return this;
+  }
+
+  u32(value: number) {
+    this.buf.writeUInt32LE(value, this.offset);
+    this.offset += 4;
+    return this;
+  }
+
+  u64(value: number | BN) {
+    return this.bytes(new BN(value).toArrayLike(Buffer, "le", 8));
+  }
+
+  i64(value: number | BN) {
+    return this.bytes(new BN(value).toTwos(64).toArrayLike(Buffer, "le", 8));
+  }
+
+  bool(value: boolean) {
+    return this.u8(value ? 1 : 0);
+  }
+
+  pubkey(value: PublicKey) {
+    return this.bytes(value.toBuffer());
+  }
+}
+
+describe("Migrate", () => {
+  let context: ProgramTestContext;
+  let program: Program<SolanaPlinkoSmartContract>;
+
+  const authority = Keypair.generate();
+  const player = Keypair.generate();
+  const feeTreasury = Keypair.generate();
+  const gameId = new BN(7);
+
+  let plinkoStatusPda: PublicKey;
+  let housePda: PublicKey;
+  let gamePda: PublicKey;
+  let userStatsPda: PublicKey;
+  let payoutTablePda: PublicKey;
+
+  const discriminator = (name: string) =>
+    IDL.accounts.find((account) => account.name === name).discriminator;
+
+  const setProgramAccount = (address: PublicKey, data: Buffer) =>
+    context.setAccount(address, {
+      lamports: LAMPORTS_PER_SOL,
+      data,
+      owner: program.programId,
+      executable: false,
+    });
+
+  const migrate = (
+    target: PublicKey,
+    signer: Keypair,
+    payoutTable: PublicKey | null = null
+  ) =>
+    program.methods
+      .migrate()
+      .accountsStrict({
+        plinkoStatus: plinkoStatusPda,
+        target,
+        payoutTable,
+        multisig: null,
+        transaction: null,
+        authority: signer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc();
+
+  before(async () => {
+    context = await startAnchor(".", [], []);
+    const provider = new BankrunProvider(context);
+    program = new Program<SolanaPlinkoSmartContract>(
+      IDL as SolanaPlinkoSmartContract,
+      provider
+    );
+
+    for (const wallet of [authority, player]) {
+      context.setAccount(wallet.publicKey, {
+        lamports: 10 * LAMPORTS_PER_SOL,
+        data: Buffer.alloc(0),
+        owner: SystemProgram.programId,
+        executable: false,
+      });
+    }
+
+    [plinkoStatusPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("plinko_status")],
+      program.programId
+    );
+    [housePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("house")],
+      program.programId
+    );
+    // Version 1 games were seeded by a global game id
+    [gamePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("game"), gameId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    // The odds of version 1 move to the Medium weighted bucket table
+    [payoutTablePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("payout_table"), Buffer.from([1]), Buffer.from([0])],
+      program.programId
+    );
+    [userStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stats"), player.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const plinkoStatus = new LegacyWriter(
+      PLINKO_STATUS_V1_SPACE,
+      discriminator("PlinkoStatus")
+    )
+      .pubkey(authority.publicKey) // owner
+      .u64(300) // platform_fee
+      .u64(10_000) // fee_denominator
+      .u64(100) // payout_denominator
+      .u64(1_000_000) // min_buy_in
+      .u8(10) // max_balls
+      .bool(true) // odds_locked
+      .bool(false) // paused
+      .u32(3) // bucket_weights
+      .u64(10)
+      .u64(20)
+      .u64(30)
+      .u32(3) // payouts
+      .u64(200)
+      .u64(50)
+      .u64(0)
+      .u64(42) // total_games
+      .u64(5_000_000_000) // total_volume
+      .u64(4_000_000_000) // total_payouts
+      .pubkey(feeTreasury.publicKey) // fee_treasury
+      .pubkey(housePda) // house_account
+      .bytes(Array(32).fill(5)) // force
+      .u8(2); // status (Finished)
+    setProgramAccount(plinkoStatusPda, plinkoStatus.buf);
+
+    const house = new LegacyWriter(HOUSE_V1_SPACE, discriminator("House"))
+      .pubkey(authority.publicKey) // owner
+      .u64(2_000_000_000) // balance
+      .u64(500_000_000) // maximum_payout
+      .u64(700_000_000) // total_payout
+      .bool(true) // withdrawals_pause
+      .u32(3); // pending_request
+    setProgramAccount(housePda, house.buf);
+
+    const game = new LegacyWriter(GAME_V1_SPACE, discriminator("Game"))
+      .u64(gameId) // game_id
+      .pubkey(player.publicKey) // player
+      .u64(2_000_000) // bet_amount
+      .u64(1_940_000) // amount_for_house
+      .u8(2) // num_balls
+      .u64(970_000) // bet_amount_per_ball
+      .u32(2) // buckets
+      .u8(4)
+      .u8(6)
+      .u64(2_500_000) // payout
+      .bool(true) // has_ended
+      .u64(1234) // request_id
+      .i64(1_700_000_000) // created_at
+      .i64(1_700_000_030); // ended_at
+    setProgramAccount(gamePda, game.buf);
+
+    const userStats = new LegacyWriter(
+      USER_STATS_V1_SPACE,
+      discriminator("UserStats")
+    )
+      .pubkey(player.publicKey) // user
+      .u64(8) // total_games
+      .u64(16_000_000) // total_wagered
+      .u64(12_000_000) // total_won
+      .u32(2) // game_ids
+      .u64(6)
+      .u64(7);
+    setProgramAccount(userStatsPda, userStats.buf);
+  });
+
+  it("rejects migrations from anyone but the owner", async () => {
+    try {
+      await migrate(housePda, player);
+      assert.fail("migration by a non-owner should fail");
+    } catch (err) {
+      assert.include(err.message, "OnlyOwner");
+    }
+  });
+
+  it("upgrades a v1 House", async () => {
+    await migrate(housePda, authority);
+
+    const house = await program.account.house.fetch(housePda);
+    assert.ok(house.owner.equals(authority.publicKey));
+    assert.equal(house.balance.toNumber(), 2_000_000_000);
+    assert.equal(house.maxLiabilityBps.toNumber(), 10_000);
+    assert.equal(house.totalPayout.toNumber(), 700_000_000);
+    assert.isTrue(house.withdrawalsPause);
+    assert.equal(house.pendingRequest, 3);
+    assert.equal(house.pendingLiability.toNumber(), 0);
+  });
+
+  it("upgrades a v1 PlinkoStatus", async () => {
+    try {
+      await migrate(plinkoStatusPda, authority);
+      assert.fail("migration without the payout table should fail");
+    } catch (err) {
+      assert.include(err.message, "MissingPayoutTable");
+    }
+    await migrate(plinkoStatusPda, authority, payoutTablePda);
+
+    const status = await program.account.plinkoStatus.fetch(plinkoStatusPda);
+    assert.ok(status.owner.equals(authority.publicKey));
+    assert.equal(status.platformFee.toNumber(), 300);
+    assert.equal(status.minBuyIn.toNumber(), 1_000_000);
+    assert.equal(status.maxBalls, 10);
+    assert.isTrue(status.oddsLocked);
+    assert.equal(status.totalGames.toNumber(), 42);
+    assert.equal(status.totalPayouts.toNumber(), 4_000_000_000);
+    assert.ok(status.feeTreasury.equals(feeTreasury.publicKey));
+    assert.ok(status.houseAccount.equals(housePda));
+    assert.equal(status.refundTimeout.toNumber(), 3_600);
+    assert.isFalse(status.refundPlatformFee);
+    assert.equal(status.gameRetention.toNumber(), 30 * 24 * 60 * 60);
+
+    const payoutTable = await program.account.payoutTable.fetch(payoutTablePda);
+    assert.deepEqual(payoutTable.riskLevel, { medium: {} });
+    assert.equal(payoutTable.rows, 0);
+    assert.deepEqual(
+      payoutTable.bucketWeights.map((weight) => weight.toNumber()),
+      [10, 20, 30]
+    );
+    assert.deepEqual(
+      payoutTable.payouts.map((payout) => payout.toNumber()),
+      [200, 50, 0]
+    );
+  });
+
+  it("upgrades a v1 Game", async () => {
+    await migrate(gamePda, authority);
+
+    const game = await program.account.game.fetch(gamePda);
+    assert.ok(game.nonce.eq(gameId));
+    assert.ok(game.player.equals(player.publicKey));
+    assert.ok(game.mint.equals(PublicKey.default));
+    assert.equal(game.betAmount.toNumber(), 2_000_000);
+    assert.equal(game.amountForHouse.toNumber(), 1_940_000);
+    assert.equal(game.platformFee.toNumber(), 60_000);
+    assert.equal(game.rows, 0);
+    assert.deepEqual(Array.from(game.buckets), [4, 6]);
+    assert.equal(game.payout.toNumber(), 2_500_000);
+    assert.deepEqual(game.status, { settled: {} });
+    assert.equal(game.requestId.toNumber(), 1234);
+    assert.equal(game.endedAt.toNumber(), 1_700_000_030);
+  });
+
+  it("refunds a pending v1 Game instead of migrating it", async () => {
+    const pendingId = new BN(8);
+    const [pendingPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("game"), pendingId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const [vaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vaultseed")],
+      program.programId
+    );
+    context.setAccount(vaultPda, {
+      lamports: 5 * LAMPORTS_PER_SOL,
+      data: Buffer.alloc(0),
+      owner: SystemProgram.programId,
+      executable: false,
+    });
+
+    // Placed two hours ago, past the one hour refund timeout
+    const now = Number((await context.banksClient.getClock()).unixTimestamp);
+    const game = new LegacyWriter(GAME_V1_SPACE, discriminator("Game"))
+      .u64(pendingId) // game_id
+      .pubkey(player.publicKey) // player
+      .u64(2_000_000) // bet_amount
+      .u64(1_940_000) // amount_for_house
+      .u8(2) // num_balls
+      .u64(970_000) // bet_amount_per_ball
+      .u32(2) // buckets
+      .u8(0)
+      .u8(0)
+      .u64(0) // payout
+      .bool(false) // has_ended
+      .u64(1235) // request_id
+      .i64(now - 2 * 60 * 60) // created_at
+      .i64(0); // ended_at
+    setProgramAccount(pendingPda, game.buf);
+
+    try {
+      await migrate(pendingPda, authority);
+      assert.fail("migration of a pending game should fail");
+    } catch (err) {
+      assert.include(err.message, "LegacyGamePending");
+    }
+
+    const closeLegacyGame = (signer: Keypair) =>
+      program.methods
+        .closeLegacyGame(pendingId)
+        .accountsStrict({
+          plinkoStatus: plinkoStatusPda,
+          game: pendingPda,
+          house: housePda,
+          vault: vaultPda,
+          player: signer.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([signer])
+        .rpc();
+
+    try {
+      await closeLegacyGame(authority);
+      assert.fail("only the player can close the game");
+    } catch (err) {
+      assert.include(err.message, "InvalidPlayer");
+    }
+
+    const balance = async (address: PublicKey) =>
+      Number(await context.banksClient.getBalance(address));
+    const playerBefore = await balance(player.publicKey);
+    const vaultBefore = await balance(vaultPda);
+    const pendingBefore = (await program.account.house.fetch(housePda)).pendingRequest;
+    await closeLegacyGame(player);
+
+    // The stake comes back from the vault, the game rent from the closed account
+    assert.equal(
+      await balance(player.publicKey),
+      playerBefore + 1_940_000 + LAMPORTS_PER_SOL
+    );
+    assert.equal(await balance(vaultPda), vaultBefore - 1_940_000);
+    assert.isNull(await context.banksClient.getAccount(pendingPda));
+
+    const house = await program.account.house.fetch(housePda);
+    assert.equal(house.pendingRequest, pendingBefore - 1);
+  });
+
+  it("upgrades a v1 UserStats", async () => {
+    await migrate(userStatsPda, authority);
+
+    const stats = await program.account.userStats.fetch(userStatsPda);
+    assert.ok(stats.user.equals(player.publicKey));
+    assert.equal(stats.totalGames.toNumber(), 8);
+    assert.equal(stats.totalWagered.toNumber(), 16_000_000);
+    assert.equal(stats.totalWon.toNumber(), 12_000_000);
+    assert.equal(stats.nextNonce.toNumber(), 0);
+  });
+
+  // Versioned accounts are told apart by their version byte, whatever their size
+  const VERSIONED_SPACE = 1_000;
+
+  it("rejects layout versions it does not know", async () => {
+    const target = Keypair.generate().publicKey;
+    const house = new LegacyWriter(VERSIONED_SPACE, discriminator("House")).u8(
+      200
+    );
+    setProgramAccount(target, house.buf);
+
+    try {
+      await migrate(target, authority);
+      assert.fail("migration of an unknown version should fail");
+    } catch (err) {
+      assert.include(err.message, "UnsupportedVersion");
+    }
+  });
+
+  it("rejects migrating an account twice", async () => {
+    try {
+      await migrate(gamePda, authority);
+      assert.fail("second migration should fail");
+    } catch (err) {
+      assert.include(err.message, "AccountAlreadyMigrated");
+    }
+  });
+});
@@ -0,0 +1,247 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram } from "@solana/web3.js";
+import { BN } from "bn.js";
+import { assert } from "chai";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  Plinko,
+  TOKEN_PROGRAM_ID,
+  expectError,
+} from "./helpers";
+
+describe("Multisig", () => {
+  let plinko: Plinko;
+  let signers: Keypair[];
+  let recipient: Keypair;
+  let withdrawId: number;
+
+  const WITHDRAWAL = LAMPORTS_PER_SOL;
+
+  const createMultisig = (signer = plinko.authority) =>
+    plinko.program.methods
+      .createMultisig(
+        signers.map((s) => s.publicKey),
+        2
+      )
+      .accountsStrict({
+        plinkoStatus: plinko.plinkoStatus,
+        multisig: plinko.multisig,
+        authority: signer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc();
+
+  const execute = async (
+    id: number,
+    accounts: { recipient?: PublicKey; roles?: boolean } = {}
+  ) => {
+    const { proposer } = await plinko.program.account.multisigTransaction.fetch(
+      plinko.multisigTransactionPda(id)
+    );
+    await plinko.program.methods
+      .executeMultisigTransaction(new BN(id))
+      .accountsStrict({
+        plinkoStatus: plinko.plinkoStatus,
+        house: plinko.house,
+        multisig: plinko.multisig,
+        transaction: plinko.multisigTransactionPda(id),
+        vault: plinko.vault,
+        recipient: accounts.recipient ?? null,
+        payoutTable: null,
+        pendingChange: null,
+        feeDistribution: null,
+        feeVault: null,
+        feeTiers: null,
+        mintConfig: null,
+        roles: accounts.roles ? plinko.roles : null,
+        proposer,
+        executor: signers[2].publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signers[2]])
+      .rpc();
+  };
+
+  const status = () => plinko.program.account.plinkoStatus.fetch(plinko.plinkoStatus);
+
+  before(async () => {
+    plinko = await Plinko.start();
+    await plinko.setup();
+    signers = [plinko.wallet(), plinko.wallet(), plinko.wallet()];
+    recipient = plinko.wallet();
+  });
+
+  it("only lets the owner create the multisig", async () => {
+    await expectError(createMultisig(signers[0]), "OnlyOwner");
+    await createMultisig();
+
+    const multisig = await plinko.program.account.multisig.fetch(plinko.multisig);
+    assert.equal(multisig.signers.length, 3);
+    assert.equal(multisig.threshold, 2);
+  });
+
+  it("only lets signers propose transactions", async () => {
+    await expectError(
+      plinko.proposeMultisig({ acceptOwner: {} }, plinko.wallet()),
+      "NotMultisigSigner"
+    );
+  });
+
+  it("has no power until it owns the program", async () => {
+    withdrawId = await plinko.proposeMultisig(
+      { withdraw: { amount: new BN(WITHDRAWAL), recipient: recipient.publicKey } },
+      signers[0]
+    );
+    await plinko.approveMultisig(withdrawId, signers[1]);
+
+    await expectError(
+      execute(withdrawId, { recipient: recipient.publicKey }),
+      "MultisigNotOwner"
+    );
+  });
+
+  it("takes ownership once enough signers approve", async () => {
+    await plinko.program.methods
+      .proposeOwner(plinko.multisig)
+      .accountsStrict({
+        plinkoStatus: plinko.plinkoStatus,
+        authority: plinko.authority.publicKey,
+      })
+      .signers([plinko.authority])
+      .rpc();
+
+    const id = await plinko.proposeMultisig({ acceptOwner: {} }, signers[0]);
+    await expectError(execute(id), "NotEnoughApprovals");
+
+    await plinko.approveMultisig(id, signers[1]);
+    await plinko.warp(1); // New slot, so the retried execution is not a duplicate
+    await execute(id);
+
+    const { owner, pendingOwner } = await status();
+    assert.ok(owner.equals(plinko.multisig));
+    assert.ok(pendingOwner.equals(PublicKey.default));
+  });
+
+  it("withdraws from the vault to the approved recipient only", async () => {
+    await expectError(
+      execute(withdrawId, { recipient: plinko.wallet().publicKey }),
+      "MissingActionAccount"
+    );
+
+    const vaultBefore = await plinko.balance(plinko.vault);
+    const recipientBefore = await plinko.balance(recipient.publicKey);
+    await plinko.warp(1);
+    await execute(withdrawId, { recipient: recipient.publicKey });
+
+    assert.equal(await plinko.balance(plinko.vault), vaultBefore - WITHDRAWAL);
+    assert.equal(
+      await plinko.balance(recipient.publicKey),
+      recipientBefore + WITHDRAWAL
+    );
+    assert.isFalse(await plinko.exists(plinko.multisigTransactionPda(withdrawId)));
+  });
+
+  it("applies parameter changes", async () => {
+    const id = await plinko.proposeMultisig(
+      { change: { 0: { platformFee: { 0: new BN(200) } } } },
+      signers[1]
+    );
+    await plinko.approveMultisig(id, signers[2]);
+    await execute(id);
+
+    assert.equal((await status()).platformFee.toNumber(), 200);
+  });
+
+  it("grants roles", async () => {
+    const treasurer = plinko.wallet();
+    const id = await plinko.proposeMultisig(
+      { grantRole: { member: treasurer.publicKey, role: { treasurer: {} } } },
+      signers[0]
+    );
+    await plinko.approveMultisig(id, signers[2]);
+    await execute(id, { roles: true });
+
+    const roles = await plinko.program.account.roles.fetch(plinko.roles);
+    assert.equal(roles.members.length, 1);
+    assert.ok(roles.members[0].member.equals(treasurer.publicKey));
+  });
+
+  it("takes the owner powers away from the old owner key", async () => {
+    await expectError(
+      plinko.program.methods
+        .proposeOwner(plinko.authority.publicKey)
+        .accountsStrict({
+          plinkoStatus: plinko.plinkoStatus,
+          authority: plinko.authority.publicKey,
+        })
+        .signers([plinko.authority])
+        .rpc(),
+      "OnlyOwner"
+    );
+  });
+
+  it("lets the proposer or a signer cancel a transaction", async () => {
+    const cancel = (id: number, signer: Keypair) =>
+      plinko.program.methods
+        .cancelMultisigTransaction(new BN(id))
+        .accountsStrict({
+          multisig: plinko.multisig,
+          transaction: plinko.multisigTransactionPda(id),
+          proposer: signers[0].publicKey,
+          authority: signer.publicKey,
+        })
+        .signers([signer])
+        .rpc();
+
+    const first = await plinko.proposeMultisig({ lockOdds: {} }, signers[0]);
+    await expectError(cancel(first, plinko.wallet()), "InvalidTransactionCanceller");
+
+    const proposerBefore = await plinko.balance(signers[0].publicKey);
+    const rent = await plinko.balance(plinko.multisigTransactionPda(first));
+    await cancel(first, signers[1]);
+    assert.isFalse(await plinko.exists(plinko.multisigTransactionPda(first)));
+    assert.equal(await plinko.balance(signers[0].publicKey), proposerBefore + rent);
+
+    const second = await plinko.proposeMultisig({ lockOdds: {} }, signers[0]);
+    await cancel(second, signers[0]);
+    assert.isFalse(await plinko.exists(plinko.multisigTransactionPda(second)));
+  });
+
+  it("runs an approved instruction only with the approved arguments", async () => {
+    const seedOwner = plinko.wallet().publicKey;
+    const id = await plinko.proposeMultisig(
+      { enableLiquidityPool: { seedShareOwner: seedOwner } },
+      signers[0]
+    );
+    await plinko.approveMultisig(id, signers[1]);
+
+    const lpMint = plinko.pda("lp_mint");
+    const enable = (owner: PublicKey) =>
+      plinko.program.methods
+        .enableLiquidityPool()
+        .accountsStrict({
+          plinkoStatus: plinko.plinkoStatus,
+          house: plinko.house,
+          vault: plinko.vault,
+          lpMint,
+          seedShareOwner: owner,
+          seedShareAccount: anchor.utils.token.associatedAddress({ mint: lpMint, owner }),
+          multisig: plinko.multisig,
+          transaction: plinko.multisigTransactionPda(id),
+          authority: signers[0].publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([signers[0]])
+        .rpc();
+
+    await expectError(enable(plinko.wallet().publicKey), "MultisigActionMismatch");
+    await enable(seedOwner);
+
+    const house = await plinko.program.account.house.fetch(plinko.house);
+    assert.isTrue(house.lpEnabled);
+    assert.isFalse(await plinko.exists(plinko.multisigTransactionPda(id)));
+  });
+});
@@ -0,0 +1,145 @@
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram } from "@solana/web3.js";
+import { BN } from "bn.js";
+import { assert } from "chai";
+import { Plinko, expectError, referralCode } from "./helpers";
+
+describe("Referrals", () => {
+  let plinko: Plinko;
+  let referrer: Keypair;
+  let player: Keypair;
+  let referral: PublicKey;
+  let escrowRent: number;
+
+  const BET = LAMPORTS_PER_SOL;
+  const FEE = 30_000_000; // 3% of the bet
+  const REFERRAL_SHARE = 6_000_000; // 20% of the platform fee
+
+  const register = (code: string) =>
+    plinko.program.methods
+      .registerReferrer(Array.from(referralCode(code)))
+      .accountsStrict({
+        referral: plinko.referralPda(code),
+        referrer: referrer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([referrer])
+      .rpc();
+
+  const bind = (signer: Keypair) =>
+    plinko.program.methods
+      .bindReferrer()
+      .accountsStrict({
+        userStats: plinko.userStatsPda(signer.publicKey),
+        referral,
+        player: signer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc();
+
+  const claim = (signer: Keypair) =>
+    plinko.program.methods
+      .claimReferralEarnings()
+      .accountsStrict({
+        referral,
+        referrer: signer.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+
+  const fetchReferral = () => plinko.program.account.referral.fetch(referral);
+
+  before(async () => {
+    plinko = await Plinko.start();
+    await plinko.setup();
+    await plinko.program.methods
+      .setReferralFee(new BN(2_000))
+      .accountsStrict({
+        plinkoStatus: plinko.plinkoStatus,
+        roles: null,
+        authority: plinko.authority.publicKey,
+      })
+      .signers([plinko.authority])
+      .rpc();
+
+    referrer = plinko.wallet();
+    player = plinko.wallet();
+    referral = plinko.referralPda("friends");
+    escrowRent = await plinko.rentExempt(0);
+  });
+
+  it("registers a referral code", async () => {
+    await expectError(register(""), "InvalidReferralCode");
+    await register("friends");
+
+    const account = await fetchReferral();
+    assert.ok(account.referrer.equals(referrer.publicKey));
+  });
+
+  it("binds a player to the code once", async () => {
+    await expectError(bind(referrer), "SelfReferral");
+    await bind(player);
+    await expectError(bind(player), "ReferrerAlreadyBound");
+
+    const userStats = await plinko.program.account.userStats.fetch(
+      plinko.userStatsPda(player.publicKey)
+    );
+    assert.ok(userStats.referrer.equals(referral));
+    assert.equal((await fetchReferral()).totalReferred.toNumber(), 1);
+  });
+
+  it("requires the bound referral on every bet", async () => {
+    await expectError(plinko.play(player, { referral: null }), "InvalidReferral");
+  });
+
+  it("pays the referrer a share of the platform fee", async () => {
+    const treasuryBefore = await plinko.balance(plinko.feeTreasury.publicKey);
+    const referralBefore = await plinko.balance(referral);
+    await plinko.play(player, { bet: BET });
+
+    assert.equal(await plinko.balance(referral), referralBefore + REFERRAL_SHARE);
+    assert.equal(
+      await plinko.balance(plinko.feeTreasury.publicKey),
+      treasuryBefore + FEE - REFERRAL_SHARE
+    );
+
+    const account = await fetchReferral();
+    assert.equal(account.totalVolume.toNumber(), BET);
+    assert.equal(account.totalEarned.toNumber(), REFERRAL_SHARE);
+    assert.equal(account.unclaimed.toNumber(), REFERRAL_SHARE);
+  });
+
+  it("lets only the referrer claim the earnings", async () => {
+    await expectError(claim(player), "OnlyReferrer");
+
+    const before = await plinko.balance(referrer.publicKey);
+    await claim(referrer);
+
+    assert.equal(await plinko.balance(referrer.publicKey), before + REFERRAL_SHARE);
+    assert.equal((await fetchReferral()).unclaimed.toNumber(), 0);
+    await expectError(claim(referrer), "NothingToClaim");
+  });
+
+  it("takes the share back when a refund returns the platform fee", async () => {
+    await plinko.setRefundTimeout(60 * 60, true);
+    const { nonce } = await plinko.play(player, { bet: BET });
+    await plinko.warp(60 * 60);
+
+    const playerBefore = await plinko.balance(player.publicKey);
+    const vaultBefore = await plinko.balance(plinko.vault);
+    await plinko.refund(player, nonce);
+
+    assert.equal(
+      await plinko.balance(player.publicKey),
+      playerBefore + BET + escrowRent
+    );
+    assert.equal(
+      await plinko.balance(plinko.vault),
+      vaultBefore - (FEE - REFERRAL_SHARE)
+    );
+
+    const account = await fetchReferral();
+    assert.equal(account.unclaimed.toNumber(), 0);
+    assert.equal(account.totalEarned.toNumber(), REFERRAL_SHARE);
+  });
+});
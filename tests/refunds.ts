@@ -0,0 +1,127 @@
+import { Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { assert } from "chai";
+import { Plinko, expectError } from "./helpers";
+
+describe("Refunds and cancellations", () => {
+  let plinko: Plinko;
+  let player: Keypair;
+  let escrowRent: number;
+
+  const BET = LAMPORTS_PER_SOL;
+  const FEE = 30_000_000; // 3% of the bet
+  const STAKE = BET - FEE;
+
+  before(async () => {
+    plinko = await Plinko.start();
+    await plinko.setup();
+    player = plinko.wallet();
+    escrowRent = await plinko.rentExempt(0);
+  });
+
+  it("holds the stake in the game's escrow", async () => {
+    const treasuryBefore = await plinko.balance(plinko.feeTreasury.publicKey);
+    const vaultBefore = await plinko.balance(plinko.vault);
+    const { escrow } = await plinko.play(player);
+
+    assert.equal(await plinko.balance(escrow), STAKE + escrowRent);
+    assert.equal(
+      await plinko.balance(plinko.feeTreasury.publicKey),
+      treasuryBefore + FEE
+    );
+    assert.equal(await plinko.balance(plinko.vault), vaultBefore);
+
+    const house = await plinko.program.account.house.fetch(plinko.house);
+    assert.equal(house.pendingRequest, 1);
+    assert.equal(house.pendingLiability.toNumber(), 2 * STAKE);
+  });
+
+  it("rejects refunds before the timeout", async () => {
+    await expectError(plinko.refund(player, 0), "GameNotExpired");
+  });
+
+  it("returns the stake from escrow once the game expires", async () => {
+    await plinko.warp(60 * 60);
+    const playerBefore = await plinko.balance(player.publicKey);
+    const vaultBefore = await plinko.balance(plinko.vault);
+    await plinko.refund(player, 0);
+
+    // The platform fee is kept by default, the escrow rent goes back with the stake
+    assert.equal(
+      await plinko.balance(player.publicKey),
+      playerBefore + STAKE + escrowRent
+    );
+    assert.equal(await plinko.balance(plinko.vault), vaultBefore);
+    assert.equal(
+      await plinko.balance(plinko.escrowPda(plinko.gamePda(player.publicKey, 0))),
+      0
+    );
+
+    const game = await plinko.program.account.game.fetch(
+      plinko.gamePda(player.publicKey, 0)
+    );
+    assert.deepEqual(game.status, { refunded: {} });
+
+    const house = await plinko.program.account.house.fetch(plinko.house);
+    assert.equal(house.pendingRequest, 0);
+    assert.equal(house.pendingLiability.toNumber(), 0);
+  });
+
+  it("returns the platform fee from the vault when configured", async () => {
+    await plinko.setRefundTimeout(60 * 60, true);
+    const { nonce } = await plinko.play(player);
+    await plinko.warp(60 * 60);
+
+    const playerBefore = await plinko.balance(player.publicKey);
+    const vaultBefore = await plinko.balance(plinko.vault);
+    await plinko.refund(player, nonce);
+
+    assert.equal(
+      await plinko.balance(player.publicKey),
+      playerBefore + BET + escrowRent
+    );
+    assert.equal(await plinko.balance(plinko.vault), vaultBefore - FEE);
+    await plinko.setRefundTimeout(60 * 60, false);
+  });
+
+  it("rejects refunds once the randomness is public", async () => {
+    const { nonce, force } = await plinko.play(player);
+    await plinko.warp(60 * 60);
+    plinko.fulfillRandomness(force);
+
+    await expectError(plinko.refund(player, nonce), "RandomnessFulfilled");
+
+    // The game can still be settled against the published randomness
+    await plinko.fulfill(player.publicKey, nonce);
+    const game = await plinko.program.account.game.fetch(
+      plinko.gamePda(player.publicKey, nonce)
+    );
+    assert.deepEqual(game.status, { settled: {} });
+  });
+
+  it("lets an operator cancel a pending game with its platform fee", async () => {
+    const { nonce } = await plinko.play(player);
+    const playerBefore = await plinko.balance(player.publicKey);
+    const vaultBefore = await plinko.balance(plinko.vault);
+
+    await expectError(
+      plinko.cancel(player.publicKey, nonce, plinko.wallet()),
+      "MissingRole"
+    );
+    await plinko.cancel(player.publicKey, nonce);
+
+    assert.equal(
+      await plinko.balance(player.publicKey),
+      playerBefore + BET + escrowRent
+    );
+    assert.equal(await plinko.balance(plinko.vault), vaultBefore - FEE);
+
+    const game = await plinko.program.account.game.fetch(
+      plinko.gamePda(player.publicKey, nonce)
+    );
+    assert.deepEqual(game.status, { cancelled: {} });
+  });
+
+  it("rejects refunds of a game that has ended", async () => {
+    await expectError(plinko.refund(player, 0), "GameAlreadyEnded");
+  });
+});
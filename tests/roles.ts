@@ -0,0 +1,128 @@
+import { Keypair, LAMPORTS_PER_SOL, SystemProgram } from "@solana/web3.js";
+import { BN } from "bn.js";
+import { assert } from "chai";
+import { Plinko, expectError } from "./helpers";
+
+describe("Roles", () => {
+  let plinko: Plinko;
+  let treasurer: Keypair;
+  let pauser: Keypair;
+  let operator: Keypair;
+  let player: Keypair;
+
+  const grantRole = (member: Keypair, role: object, signer = plinko.authority) =>
+    plinko.program.methods
+      .grantRole(member.publicKey, role as any)
+      .accountsStrict({
+        plinkoStatus: plinko.plinkoStatus,
+        roles: plinko.roles,
+        authority: signer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc();
+
+  const revokeRole = (member: Keypair, role: object) =>
+    plinko.program.methods
+      .revokeRole(member.publicKey, role as any)
+      .accountsStrict({
+        plinkoStatus: plinko.plinkoStatus,
+        roles: plinko.roles,
+        authority: plinko.authority.publicKey,
+      })
+      .signers([plinko.authority])
+      .rpc();
+
+  const withdraw = (signer: Keypair, amount: number) =>
+    plinko.program.methods
+      .withdrawFromVault(new BN(amount))
+      .accountsStrict({
+        plinkoStatus: plinko.plinkoStatus,
+        house: plinko.house,
+        roles: plinko.roles,
+        authority: signer.publicKey,
+        vault: plinko.vault,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc();
+
+  const setPaused = (signer: Keypair, paused: boolean) =>
+    plinko.program.methods
+      .setPaused(paused)
+      .accountsStrict({
+        plinkoStatus: plinko.plinkoStatus,
+        roles: plinko.roles,
+        authority: signer.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+
+  before(async () => {
+    plinko = await Plinko.start();
+    await plinko.setup(10 * LAMPORTS_PER_SOL);
+    treasurer = plinko.wallet();
+    pauser = plinko.wallet();
+    operator = plinko.wallet();
+    player = plinko.wallet();
+  });
+
+  it("only lets the owner grant roles", async () => {
+    await expectError(
+      grantRole(treasurer, { treasurer: {} }, treasurer),
+      "OnlyOwner"
+    );
+
+    await grantRole(treasurer, { treasurer: {} });
+    await grantRole(pauser, { pauser: {} });
+    await grantRole(operator, { operator: {} });
+
+    const roles = await plinko.program.account.roles.fetch(plinko.roles);
+    assert.equal(roles.members.length, 3);
+  });
+
+  it("lets a treasurer withdraw from the vault", async () => {
+    const before = await plinko.balance(treasurer.publicKey);
+    await withdraw(treasurer, LAMPORTS_PER_SOL);
+
+    assert.equal(
+      await plinko.balance(treasurer.publicKey),
+      before + LAMPORTS_PER_SOL
+    );
+    assert.equal(await plinko.balance(plinko.vault), 9 * LAMPORTS_PER_SOL);
+  });
+
+  it("keeps the other roles away from the vault", async () => {
+    await expectError(withdraw(pauser, LAMPORTS_PER_SOL), "MissingRole");
+    await expectError(withdraw(operator, LAMPORTS_PER_SOL), "MissingRole");
+    await expectError(withdraw(player, LAMPORTS_PER_SOL), "MissingRole");
+  });
+
+  it("lets a pauser stop bets", async () => {
+    await expectError(setPaused(treasurer, true), "MissingRole");
+
+    await setPaused(pauser, true);
+    await expectError(plinko.play(player), "GamePaused");
+
+    await setPaused(pauser, false);
+    await plinko.play(player);
+  });
+
+  it("lets an operator cancel a pending game", async () => {
+    await plinko.cancel(player.publicKey, 0, operator);
+
+    const game = await plinko.program.account.game.fetch(
+      plinko.gamePda(player.publicKey, 0)
+    );
+    assert.deepEqual(game.status, { cancelled: {} });
+  });
+
+  it("takes a role away on revoke", async () => {
+    await revokeRole(treasurer, { treasurer: {} });
+    await expectError(withdraw(treasurer, LAMPORTS_PER_SOL / 2), "MissingRole");
+    await expectError(revokeRole(pauser, { treasurer: {} }), "RoleNotGranted");
+
+    const roles = await plinko.program.account.roles.fetch(plinko.roles);
+    assert.equal(roles.members.length, 2);
+  });
+});
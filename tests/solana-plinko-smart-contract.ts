@@ -34,6 +34,8 @@ const program = anchor.workspace
 
 let gamePda: PublicKey;
 let userStatsPda: PublicKey;
+let historyPagePda: PublicKey;
+let escrowPda: PublicKey;
 let authority: Keypair;
 let feeTreasury: Keypair;
 let player: Keypair;
@@ -72,6 +74,11 @@ const vrf = new Orao(provider);
 describe("Initialize", async () => {
   let plinkoStatusPda: PublicKey;
   let housePda: PublicKey;
+  let payoutTablePda: PublicKey;
+
+  // Board mode: 10 peg rows land in one of 11 buckets
+  const riskLevel = { medium: {} };
+  const rows = 10;
 
   [plinkoStatusPda] = await PublicKey.findProgramAddressSync(
     [Buffer.from("plinko_status")],
@@ -82,6 +89,11 @@ describe("Initialize", async () => {
     program.programId
   );
 
+  [payoutTablePda] = await PublicKey.findProgramAddressSync(
+    [Buffer.from("payout_table"), Buffer.from([1]), Buffer.from([rows])],
+    program.programId
+  );
+
   console.log("🚀 ~ describe ~ plinkoStatusPda:", plinkoStatusPda);
   console.log("🚀 ~ describe ~ housePda:", housePda);
 
@@ -148,17 +160,17 @@ describe("Initialize", async () => {
       (n) => new BN(n)
     );
 
-    const weights = [1, 2, 3, 5, 8, 12, 16, 19, 21, 22, 23].map(
-      (n) => new BN(n)
-    );
     console.log("🚀 ~ it ~ payouts:", payouts);
-    console.log("🚀 ~ it ~ weights:", weights);
+    console.log("🚀 ~ it ~ rows:", rows);
 
     try {
       const ix = await program.methods
-        .setPayout(payouts, weights)
-        .accounts({
+        .setPayout(riskLevel, rows, [], payouts)
+        .accountsPartial({
           authority: authority.publicKey,
+          payoutTable: payoutTablePda,
+          // The owner holds every role, the roles account is only needed for delegates
+          roles: null,
         })
         .instruction();
 
@@ -173,18 +185,15 @@ describe("Initialize", async () => {
     } catch (err) {
       console.log("Set Payout Error: ", err.message);
     }
-    const plinko_status = await program.account.plinkoStatus.fetch(
-      plinkoStatusPda
+    const payout_table = await program.account.payoutTable.fetch(
+      payoutTablePda
     );
-    console.log("Plinko Set payouts status:", plinko_status.oddsLocked);
+    console.log("Payout table locked:", payout_table.locked);
     console.log(
       "Payouts set successfully:",
-      plinko_status.payouts.map((p) => p.toString())
-    );
-    console.log(
-      "Bucket IDs set successfully:",
-      plinko_status.bucketWeights.map((b) => b.toString())
+      payout_table.payouts.map((p) => p.toString())
     );
+    console.log("Board rows set successfully:", payout_table.rows);
   });
 
   it("should lock the odds", async () => {
@@ -212,7 +221,7 @@ describe("Initialize", async () => {
 
   const forceKeypair = anchor.web3.Keypair.generate();
   const forceBytes = forceKeypair.publicKey.toBuffer();
-  const gameId = new BN(1);
+  let nonce = new BN(0);
 
   it("should allow a player to start a game", async () => {
     let vaultPda: PublicKey;
@@ -223,12 +232,37 @@ describe("Initialize", async () => {
     const treasury = new PublicKey(
       "9ZTHWWZDpB36UFe1vszf2KEpt83vwi27jDqtHQ7NSXyR"
     );
+    [userStatsPda] = await PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stats"), player.publicKey.toBuffer()],
+      program.programId
+    );
+    // Games are numbered per player, the next nonce comes from the player's stats
+    const existingStats = await program.account.userStats.fetchNullable(
+      userStatsPda
+    );
+    if (existingStats) {
+      nonce = existingStats.nextNonce;
+    }
     [gamePda] = await PublicKey.findProgramAddressSync(
-      [Buffer.from("game"), gameId.toArrayLike(Buffer, "le", 8)],
+      [
+        Buffer.from("game"),
+        player.publicKey.toBuffer(),
+        nonce.toArrayLike(Buffer, "le", 8),
+      ],
       program.programId
     );
-    [userStatsPda] = await PublicKey.findProgramAddressSync(
-      [Buffer.from("user_stats"), player.publicKey.toBuffer()],
+    // Each history page holds 50 games
+    [historyPagePda] = await PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("history"),
+        player.publicKey.toBuffer(),
+        nonce.divn(50).toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+    // The stake sits in the game's escrow until settlement
+    [escrowPda] = await PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow"), gamePda.toBuffer()],
       program.programId
     );
     [vaultPda] = await PublicKey.findProgramAddressSync(
@@ -251,12 +285,15 @@ describe("Initialize", async () => {
     console.log("randomPda (JS):", randomPda.toBase58());
     try {
       const tx = await program.methods
-        .playGame([...forceBytes], gameId, numBalls, betBn)
+        .playGame([...forceBytes], numBalls, riskLevel, rows, betBn, new BN(0))
         .accountsStrict({
           plinkoStatus: plinkoStatusPda,
+          payoutTable: payoutTablePda,
           game: gamePda,
           house: housePda,
           userStats: userStatsPda,
+          historyPage: historyPagePda,
+          escrow: escrowPda,
           vault: vaultPda,
           player: player.publicKey,
           treasury: treasury,
@@ -264,6 +301,11 @@ describe("Initialize", async () => {
           config: configPda,
           vrf: vrf.programId,
           feeTreasury: feeTreasury.publicKey,
+          referral: null,
+          integrator: null,
+          integratorStats: null,
+          feeTiers: null,
+          jackpot: null,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([player])
@@ -282,13 +324,20 @@ describe("Initialize", async () => {
     const game = await program.account.game.fetch(gamePda);
 
     console.log("Game created successfully:", game);
-    console.log("Game ID:", game.gameId.toString());
+    console.log("Game nonce:", game.nonce.toString());
     console.log("Player:", game.player.toBase58());
     console.log("Total Amount Bet of User:", game.betAmount.toNumber());
     console.log("Amount for house:", game.amountForHouse.toNumber());
     console.log("Bet Amount per ball:", game.betAmountPerBall.toNumber());
     console.log("Number of Balls:", game.numBalls);
-    console.log("Has Ended:", game.hasEnded);
+    console.log("Rows:", game.rows);
+    console.log("Status:", game.status);
+    console.log("Randomness account:", game.random.toBase58());
+    assert.ok(game.random.equals(randomPda));
+
+    const escrowBalance = await connection.getBalance(escrowPda);
+    console.log("Escrow balance:", escrowBalance);
+    assert.isAtLeast(escrowBalance, game.amountForHouse.toNumber());
 
     const user_stats = await program.account.userStats.fetch(userStatsPda);
 
@@ -297,8 +346,17 @@ describe("Initialize", async () => {
     console.log("User total bet amount: ", user_stats.totalWagered.toNumber());
     console.log("User total won: ", user_stats.totalWon.toNumber());
     console.log(
-      "User games ids: ",
-      user_stats.gameIds.map((id) => id.toString())
+      "User recent games: ",
+      user_stats.recentGames.map((g) => g.nonce.toString())
+    );
+
+    const history_page = await program.account.historyPage.fetch(
+      historyPagePda
+    );
+    console.log("History page: ", history_page.page.toString());
+    console.log(
+      "History page games: ",
+      history_page.records.map((r) => r.nonce.toString())
     );
 
     const plinko_status = await program.account.plinkoStatus.fetch(
@@ -309,11 +367,6 @@ describe("Initialize", async () => {
       plinko_status.totalGames.toNumber()
     );
     console.log("Plinko total volume: ", plinko_status.totalVolume.toNumber());
-    console.log(
-      "Plinko Game Force: ",
-      plinko_status.force.map((f) => f.toString())
-    );
-    console.log("Plinko Game Status: ", plinko_status.status);
 
     const house = await program.account.house.fetch(housePda);
 
@@ -326,7 +379,11 @@ describe("Initialize", async () => {
 
     const randomPda = randomnessAccountAddress(forceBytes);
     [gamePda] = await PublicKey.findProgramAddressSync(
-      [Buffer.from("game"), gameId.toArrayLike(Buffer, "le", 8)],
+      [
+        Buffer.from("game"),
+        player.publicKey.toBuffer(),
+        nonce.toArrayLike(Buffer, "le", 8),
+      ],
       program.programId
     );
     [userStatsPda] = await PublicKey.findProgramAddressSync(
@@ -344,16 +401,22 @@ describe("Initialize", async () => {
     console.log("Player Public Key: ", player.publicKey);
     try {
       const ix = await program.methods
-        .fulfillRandomWords([...forceBytes], gameId, requestId)
+        .fulfillRandomWords([...forceBytes], nonce, requestId)
         .accountsStrict({
           player: player.publicKey,
           game: gamePda,
+          payoutTable: payoutTablePda,
           house: housePda,
           plinkoStatus: plinkoStatusPda,
           vault: vaultPda,
           random: randomPda,
           systemProgram: SystemProgram.programId,
           userStats: userStatsPda,
+          historyPage: historyPagePda,
+          escrow: escrowPda,
+          feeTreasury: feeTreasury.publicKey,
+          referral: null,
+          jackpot: null,
         })
         .instruction();
 
@@ -376,21 +439,28 @@ describe("Initialize", async () => {
     const plinko_game = await program.account.game.fetch(gamePda);
 
     console.log("Game Buckets Index Array: ", Array.from(plinko_game.buckets));
+    console.log("Game Ball Paths: ", Array.from(plinko_game.paths));
     console.log("After bet Game Total Payout: ", plinko_game.payout.toString());
 
     const house = await program.account.house.fetch(housePda);
     console.log("House pending request: ", house.pendingRequest);
     console.log("Current House balance: ", house.balance.toString());
 
-    console.log("Game has ended: ", game.hasEnded);
+    console.log("Game status: ", plinko_game.status);
+
+    // Settlement empties the escrow, losses are swept into the vault
+    assert.equal(await connection.getBalance(escrowPda), 0);
 
     const user_stats = await program.account.userStats.fetch(userStatsPda);
     console.log("User total won: ", user_stats.totalWon.toNumber());
-    console.log("User's TotalGame Count: ", user_stats.totalGames.toNumber()),
-      console.log(
-        "User's GameIds: ",
-        user_stats.gameIds.map((g) => g.toString())
-      );
+    console.log("User's TotalGame Count: ", user_stats.totalGames.toNumber());
+
+    const history_page = await program.account.historyPage.fetch(
+      historyPagePda
+    );
+    const record = history_page.records[nonce.modn(50)];
+    console.log("History record status: ", record.status);
+    console.log("History record payout: ", record.payout.toString());
   });
 
   it("Withdraw from vault pda to admin wallet", async () => {
@@ -398,6 +468,7 @@ describe("Initialize", async () => {
       .withdrawFromVault(BN[10_000])
       .accounts({
         authority: authority.publicKey,
+        roles: null,
       })
       .instruction();
 
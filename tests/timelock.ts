@@ -0,0 +1,116 @@
+import { Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { BN } from "bn.js";
+import { assert } from "chai";
+import { Plinko, expectError } from "./helpers";
+
+describe("Change timelock", () => {
+  let plinko: Plinko;
+  let player: Keypair;
+
+  const DELAY = 60 * 60;
+
+  const setPlatformFee = (fee: number) =>
+    plinko.program.methods
+      .setPlatformFee(new BN(fee))
+      .accountsStrict({
+        plinkoStatus: plinko.plinkoStatus,
+        roles: null,
+        authority: plinko.authority.publicKey,
+      })
+      .signers([plinko.authority])
+      .rpc();
+
+  const cancelChange = async (id: number, signer: Keypair) => {
+    const { proposer } = await plinko.program.account.pendingChange.fetch(
+      plinko.changePda(id)
+    );
+    await plinko.program.methods
+      .cancelChange(new BN(id))
+      .accountsStrict({
+        plinkoStatus: plinko.plinkoStatus,
+        pendingChange: plinko.changePda(id),
+        proposer,
+        authority: signer.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+  };
+
+  before(async () => {
+    plinko = await Plinko.start();
+    await plinko.setup();
+    player = plinko.wallet();
+  });
+
+  it("applies changes right away while there is no delay", async () => {
+    const id = await plinko.proposeChange({ changeDelay: { 0: new BN(DELAY) } });
+    await plinko.executeChange(id);
+
+    const status = await plinko.program.account.plinkoStatus.fetch(
+      plinko.plinkoStatus
+    );
+    assert.equal(status.changeDelay.toNumber(), DELAY);
+    assert.isFalse(await plinko.exists(plinko.changePda(id)));
+  });
+
+  it("blocks the direct setters once a delay is set", async () => {
+    await expectError(setPlatformFee(200), "ChangeTimelocked");
+  });
+
+  it("only lets keys with the role propose a change", async () => {
+    await expectError(
+      plinko.proposeChange({ platformFee: { 0: new BN(200) } }, plinko.wallet()),
+      "MissingRole"
+    );
+  });
+
+  it("applies a queued fee change after the delay", async () => {
+    const id = await plinko.proposeChange({ platformFee: { 0: new BN(200) } });
+    await expectError(plinko.executeChange(id), "ChangeNotReady");
+
+    await plinko.warp(DELAY);
+    await plinko.executeChange(id, {}, plinko.wallet());
+
+    // Bets pay the new 2% fee to the treasury
+    const treasuryBefore = await plinko.balance(plinko.feeTreasury.publicKey);
+    await plinko.play(player);
+    assert.equal(
+      await plinko.balance(plinko.feeTreasury.publicKey),
+      treasuryBefore + LAMPORTS_PER_SOL / 50
+    );
+  });
+
+  it("holds queued changes back when the delay is raised", async () => {
+    const id = await plinko.proposeChange({ maxBalls: { 0: 5 } });
+    const raise = await plinko.proposeChange({ changeDelay: { 0: new BN(2 * DELAY) } });
+    await plinko.warp(DELAY);
+    await plinko.executeChange(raise);
+
+    // Queued under the old delay, it now waits for the new one
+    await expectError(plinko.executeChange(id), "ChangeNotReady");
+    await plinko.warp(DELAY);
+    await plinko.executeChange(id);
+
+    const status = await plinko.program.account.plinkoStatus.fetch(
+      plinko.plinkoStatus
+    );
+    assert.equal(status.maxBalls, 5);
+  });
+
+  it("lets the proposer or the owner cancel a change", async () => {
+    const id = await plinko.proposeChange({ minBuyIn: { 0: new BN(LAMPORTS_PER_SOL) } });
+    await expectError(cancelChange(id, plinko.wallet()), "InvalidChangeCanceller");
+
+    const before = await plinko.balance(plinko.authority.publicKey);
+    const rent = await plinko.balance(plinko.changePda(id));
+    await cancelChange(id, plinko.authority);
+
+    assert.isFalse(await plinko.exists(plinko.changePda(id)));
+    assert.equal(await plinko.balance(plinko.authority.publicKey), before + rent);
+
+    const status = await plinko.program.account.plinkoStatus.fetch(
+      plinko.plinkoStatus
+    );
+    assert.equal(status.minBuyIn.toNumber(), 1_000_000);
+  });
+});
@@ -0,0 +1,203 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { BN } from "bn.js";
+import { assert } from "chai";
+import { Plinko, TokenGame, TOKEN_PROGRAM_ID, expectError } from "./helpers";
+
+describe("Token bets", () => {
+  let plinko: Plinko;
+  let player: Keypair;
+  let token: TokenGame;
+  let tokenVault: PublicKey;
+
+  const VAULT_AMOUNT = 1_000_000_000;
+  const BET = 1_000_000;
+  const FEE = 30_000; // 3% of the bet
+  const STAKE = BET - FEE;
+
+  const setMintConfig = (maxLiabilityBps: number, enabled: boolean) =>
+    plinko.program.methods
+      .setMintConfig(new BN(300), new BN(1_000), new BN(maxLiabilityBps), enabled)
+      .accountsStrict({
+        plinkoStatus: plinko.plinkoStatus,
+        mintConfig: plinko.mintConfigPda(token.mint),
+        roles: null,
+        authority: plinko.authority.publicKey,
+      })
+      .signers([plinko.authority])
+      .rpc();
+
+  const refundToken = async (nonce: number) => {
+    const game = plinko.gamePda(player.publicKey, nonce);
+    const { force } = await plinko.program.account.game.fetch(game);
+    await plinko.program.methods
+      .refundExpiredGameToken(new BN(nonce))
+      .accountsStrict({
+        plinkoStatus: plinko.plinkoStatus,
+        game,
+        userStats: plinko.userStatsPda(player.publicKey),
+        historyPage: plinko.historyPagePda(player.publicKey, nonce),
+        mintConfig: plinko.mintConfigPda(token.mint),
+        mint: token.mint,
+        vault: plinko.vault,
+        vaultTokenAccount: tokenVault,
+        escrowTokenAccount: plinko.escrowPda(game),
+        random: plinko.randomPda(force),
+        player: player.publicKey,
+        playerTokenAccount: token.playerTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([player])
+      .rpc();
+  };
+
+  before(async () => {
+    plinko = await Plinko.start();
+    await plinko.setup();
+    await plinko.setPayout(50, "low");
+
+    const mint = plinko.createMint();
+    await plinko.addMint(mint);
+    tokenVault = plinko.tokenVaultPda(mint);
+    await plinko.setTokenBalance(tokenVault, VAULT_AMOUNT);
+
+    player = plinko.wallet();
+    token = {
+      mint,
+      playerTokenAccount: plinko.createTokenAccount(mint, player.publicKey, 10 * BET),
+      feeTreasuryTokenAccount: plinko.createTokenAccount(
+        mint,
+        plinko.feeTreasury.publicKey
+      ),
+    };
+  });
+
+  it("escrows the stake and sends the platform fee to the treasury", async () => {
+    const { game, escrow } = await plinko.playToken(player, token, BET);
+
+    assert.equal(await plinko.tokenBalance(token.playerTokenAccount), 9 * BET);
+    assert.equal(await plinko.tokenBalance(token.feeTreasuryTokenAccount), FEE);
+    assert.equal(await plinko.tokenBalance(escrow), STAKE);
+    assert.equal(await plinko.tokenBalance(tokenVault), VAULT_AMOUNT);
+
+    const gameAccount = await plinko.program.account.game.fetch(game);
+    assert.ok(gameAccount.mint.equals(token.mint));
+    assert.equal(gameAccount.amountForHouse.toNumber(), STAKE);
+    assert.equal(gameAccount.platformFee.toNumber(), FEE);
+
+    const mintConfig = await plinko.program.account.mintConfig.fetch(
+      plinko.mintConfigPda(token.mint)
+    );
+    assert.equal(mintConfig.pendingRequest, 1);
+    assert.equal(mintConfig.pendingLiability.toNumber(), 2 * STAKE);
+    assert.equal(mintConfig.totalVolume.toNumber(), BET);
+  });
+
+  it("pays a winning game from escrow and the vault", async () => {
+    await plinko.fulfillToken(player.publicKey, 0, token);
+
+    // 2x board: the stake comes back from escrow and the vault adds as much again
+    assert.equal(
+      await plinko.tokenBalance(token.playerTokenAccount),
+      9 * BET + 2 * STAKE
+    );
+    assert.equal(await plinko.tokenBalance(tokenVault), VAULT_AMOUNT - STAKE);
+    const gamePda = plinko.gamePda(player.publicKey, 0);
+    assert.isFalse(await plinko.exists(plinko.escrowPda(gamePda)));
+
+    const game = await plinko.program.account.game.fetch(gamePda);
+    assert.deepEqual(game.status, { settled: {} });
+    assert.equal(game.payout.toNumber(), 2 * STAKE);
+
+    const mintConfig = await plinko.program.account.mintConfig.fetch(
+      plinko.mintConfigPda(token.mint)
+    );
+    assert.equal(mintConfig.pendingRequest, 0);
+    assert.equal(mintConfig.pendingLiability.toNumber(), 0);
+    assert.equal(mintConfig.totalPayouts.toNumber(), 2 * STAKE);
+    assert.equal(mintConfig.balance.toNumber(), VAULT_AMOUNT - STAKE);
+  });
+
+  it("keeps the lost part of the stake in the vault", async () => {
+    const playerBefore = await plinko.tokenBalance(token.playerTokenAccount);
+    const vaultBefore = await plinko.tokenBalance(tokenVault);
+    const { nonce } = await plinko.playToken(player, token, BET, 1, "low");
+    await plinko.fulfillToken(player.publicKey, nonce, token);
+
+    // 0.5x board: half the stake goes back to the player, the rest to the vault
+    assert.equal(
+      await plinko.tokenBalance(token.playerTokenAccount),
+      playerBefore - BET + STAKE / 2
+    );
+    assert.equal(await plinko.tokenBalance(tokenVault), vaultBefore + STAKE / 2);
+  });
+
+  it("refunds the stake of an expired game", async () => {
+    const playerBefore = await plinko.tokenBalance(token.playerTokenAccount);
+    const { nonce, escrow } = await plinko.playToken(player, token, BET);
+
+    await expectError(refundToken(nonce), "GameNotExpired");
+
+    await plinko.warp(60 * 60);
+    await refundToken(nonce);
+
+    // The platform fee stays with the treasury unless refunds return it
+    assert.equal(
+      await plinko.tokenBalance(token.playerTokenAccount),
+      playerBefore - FEE
+    );
+    assert.isFalse(await plinko.exists(escrow));
+
+    const game = await plinko.program.account.game.fetch(
+      plinko.gamePda(player.publicKey, nonce)
+    );
+    assert.deepEqual(game.status, { refunded: {} });
+
+    const mintConfig = await plinko.program.account.mintConfig.fetch(
+      plinko.mintConfigPda(token.mint)
+    );
+    assert.equal(mintConfig.pendingRequest, 0);
+    assert.equal(mintConfig.pendingLiability.toNumber(), 0);
+  });
+
+  it("caps the pending liability of the mint vault", async () => {
+    // 0.01% of the vault covers less than the 2x worst case of a bet
+    await setMintConfig(1, true);
+    await expectError(
+      plinko.playToken(player, token, BET),
+      "ExposureLimitExceeded"
+    );
+    await setMintConfig(10_000, true);
+  });
+
+  it("rejects bets in a disabled mint", async () => {
+    await setMintConfig(10_000, false);
+    await expectError(plinko.playToken(player, token, BET), "MintDisabled");
+  });
+
+  it("queues token setting changes behind the change delay", async () => {
+    const delay = 60 * 60;
+    await plinko.executeChange(
+      await plinko.proposeChange({ changeDelay: { 0: new BN(delay) } })
+    );
+    await expectError(setMintConfig(10_000, true), "ChangeTimelocked");
+
+    const mintConfig = plinko.mintConfigPda(token.mint);
+    const id = await plinko.proposeChange({
+      mintConfig: {
+        mint: token.mint,
+        platformFee: new BN(200),
+        minBuyIn: new BN(1_000),
+        maxLiabilityBps: new BN(10_000),
+        enabled: true,
+      },
+    });
+    await expectError(plinko.executeChange(id, { mintConfig }), "ChangeNotReady");
+
+    await plinko.warp(delay);
+    await plinko.executeChange(id, { mintConfig });
+
+    const config = await plinko.program.account.mintConfig.fetch(mintConfig);
+    assert.equal(config.platformFee.toNumber(), 200);
+    assert.isTrue(config.enabled);
+  });
+});